@@ -668,6 +668,156 @@ impl FlagSet {
     }
 }
 
+/// Attempts to extend a partial isomorphism between two polytopes to the
+/// component of a given flag, by sending it to a given flag of the other
+/// polytope and then following flag changes. Returns `false` if this gives an
+/// inconsistent map, in which case the maps are left in an unspecified state.
+fn extend_isomorphism(
+    p: &Abstract,
+    q: &Abstract,
+    flag: Flag,
+    image: Flag,
+    map: &mut [Vec<Option<usize>>],
+    inverse: &mut [Vec<Option<usize>>],
+    visited: &mut HashSet<Flag>,
+) -> bool {
+    let rank = p.rank();
+    let mut queue = VecDeque::new();
+    queue.push_back((flag, image));
+
+    while let Some((flag, image)) = queue.pop_front() {
+        // Checks that the elements of the flags correspond to each other.
+        for r in 0..=rank {
+            let (a, b) = (flag[r], image[r]);
+            match (map[r][a], inverse[r][b]) {
+                (None, None) => {
+                    map[r][a] = Some(b);
+                    inverse[r][b] = Some(a);
+                }
+                (Some(x), Some(y)) if x == b && y == a => {}
+                _ => return false,
+            }
+        }
+
+        if visited.insert(flag.clone()) {
+            for r in 1..rank {
+                queue.push_back((flag.change(p, r), image.change(q, r)));
+            }
+        }
+    }
+
+    true
+}
+
+/// Attempts to extend a partial isomorphism between two polytopes to a full
+/// one, by trying every possible image for a flag in each component of the
+/// flag graph that hasn't been visited yet. Returns whether this succeeded.
+///
+/// Since components can always be swapped with isomorphic ones, we can keep
+/// the first image that works for every component.
+fn complete_isomorphism(
+    p: &Abstract,
+    q: &Abstract,
+    mut map: Vec<Vec<Option<usize>>>,
+    mut inverse: Vec<Vec<Option<usize>>>,
+    mut visited: HashSet<Flag>,
+) -> bool {
+    let images: Vec<Flag> = q.flags().collect();
+
+    for flag in p.flags() {
+        if visited.contains(&flag) {
+            continue;
+        }
+
+        let mut found = false;
+        for image in &images {
+            let mut new_map = map.clone();
+            let mut new_inverse = inverse.clone();
+            let mut new_visited = visited.clone();
+
+            if extend_isomorphism(
+                p,
+                q,
+                flag.clone(),
+                image.clone(),
+                &mut new_map,
+                &mut new_inverse,
+                &mut new_visited,
+            ) {
+                map = new_map;
+                inverse = new_inverse;
+                visited = new_visited;
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            return false;
+        }
+    }
+
+    // Every element of the second polytope must have been reached.
+    inverse.iter().flatten().all(Option::is_some)
+}
+
+/// Returns an empty partial map between the elements of a polytope and those
+/// of another with the same element counts.
+fn empty_map(p: &Abstract) -> Vec<Vec<Option<usize>>> {
+    p.el_count_iter().map(|n| vec![None; n]).collect()
+}
+
+/// Determines whether two polytopes are isomorphic, i.e. whether there's a
+/// bijection between their elements that preserves rank and incidence.
+///
+/// An isomorphism is determined by the image of a single flag in every
+/// component of the flag graph. We first compare element counts, and then try
+/// every possible image for a flag in each component.
+///
+/// # Panics
+/// You must call [`Polytope::element_sort`] on both polytopes before calling
+/// this method.
+pub fn is_isomorphic(p: &Abstract, q: &Abstract) -> bool {
+    if !p.el_count_iter().eq(q.el_count_iter()) {
+        return false;
+    }
+
+    if p.rank() <= 1 {
+        return true;
+    }
+
+    complete_isomorphism(p, q, empty_map(p), empty_map(q), HashSet::new())
+}
+
+/// Determines whether the automorphism group of a polytope acts transitively
+/// on its flags. We do this by checking that the first flag can be sent to
+/// any other by an automorphism.
+///
+/// # Panics
+/// You must call [`Polytope::element_sort`] before calling this method.
+pub fn is_flag_transitive(p: &Abstract) -> bool {
+    if p.rank() <= 1 {
+        return true;
+    }
+
+    let first_flag = p.first_flag();
+    p.flags().all(|flag| {
+        let mut map = empty_map(p);
+        let mut inverse = map.clone();
+        let mut visited = HashSet::new();
+
+        extend_isomorphism(
+            p,
+            p,
+            first_flag.clone(),
+            flag,
+            &mut map,
+            &mut inverse,
+            &mut visited,
+        ) && complete_isomorphism(p, p, map, inverse, visited)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -686,6 +836,16 @@ mod tests {
         );
     }
 
+    /// Checks that counting flags stops as soon as it goes over budget.
+    #[test]
+    fn budget() {
+        let mut hypercube = Abstract::hypercube(8);
+        hypercube.element_sort();
+
+        assert!(hypercube.try_flag_count(Some(10)).is_err());
+        assert_eq!(hypercube.try_flag_count(None).unwrap(), 645120);
+    }
+
     /// Checks a nullitope's flags.
     #[test]
     fn nullitope() {
@@ -987,4 +1147,51 @@ mod tests {
         test_flags(&mut snic, 240);
         test_flags(&mut ti, 360);
     }
+
+    /// Checks whether two polytopes are isomorphic after sorting them.
+    fn isomorphic(mut p: Abstract, mut q: Abstract) -> bool {
+        p.element_sort();
+        q.element_sort();
+        is_isomorphic(&p, &q)
+    }
+
+    /// Checks that differently ordered constructions of the cube are
+    /// isomorphic.
+    #[test]
+    fn isomorphic_cubes() {
+        let cube = Abstract::cube();
+        assert!(isomorphic(cube.clone(), Abstract::octahedron().try_dual().unwrap()));
+        assert!(isomorphic(cube.clone(), Abstract::polygon(4).prism()));
+        assert!(isomorphic(
+            cube,
+            Abstract::dyad().duoprism(&Abstract::polygon(4))
+        ));
+    }
+
+    #[test]
+    fn not_isomorphic() {
+        assert!(!isomorphic(Abstract::cube(), Abstract::octahedron()));
+
+        // These have the same element counts.
+        let triangles = Abstract::compound(iter::repeat_n(Abstract::polygon(3), 2));
+        assert!(!isomorphic(Abstract::polygon(6), triangles.clone()));
+        assert!(isomorphic(triangles.clone(), triangles));
+    }
+
+    /// Checks a few polytopes that are regular as abstract polytopes.
+    #[test]
+    fn regular() {
+        assert!(Abstract::cube().is_regular_abstract());
+        assert!(Abstract::polygon(5).is_regular_abstract());
+        assert!(Abstract::simplex(5).is_regular_abstract());
+        assert!(Abstract::dyad().is_regular_abstract());
+        assert_eq!(Abstract::cube().flag_count(), 48);
+    }
+
+    /// Checks a few polytopes that aren't regular as abstract polytopes.
+    #[test]
+    fn not_regular() {
+        assert!(!Abstract::cube().truncate().is_regular_abstract());
+        assert!(!Abstract::polygon(3).duoprism(&Abstract::polygon(4)).is_regular_abstract());
+    }
 }
@@ -7,7 +7,7 @@ pub mod ranked;
 pub mod valid;
 
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     convert::Infallible,
     ops::{Index, IndexMut},
     slice, vec, iter,
@@ -97,6 +97,11 @@ impl Metadata {
 /// For more info, see [Wikipedia](https://en.wikipedia.org/wiki/Abstract_polytope)
 /// or the [Polytope Wiki](https://polytope.miraheze.org/wiki/Abstract_polytope).
 ///
+/// An `Abstract` stores no geometric data whatsoever, so anything purely
+/// combinatorial, like taking products, duals, or iterating over flags, can be
+/// done without ever touching coordinates. A [`Concrete`](crate::conc::Concrete)
+/// polytope is just an `Abstract` together with a list of vertices.
+///
 /// # An invariant
 /// Every method you call on an `Abstract` must be able to assume that its input
 /// is a valid polytope. Furthermore, every single method that returns an
@@ -265,6 +270,64 @@ impl Abstract {
         Some((element_hash.to_vertices(), element_hash.to_polytope(self)))
     }
 
+    /// Builds the vertex figure of the vertex with a given index directly from
+    /// the elements containing it, or returns `None` if the vertex doesn't
+    /// exist. Unlike [`Polytope::verf`], this doesn't take the dual of the
+    /// entire polytope.
+    pub fn vertex_figure(&self, idx: usize) -> Option<Self> {
+        self.get_element(1, idx)?;
+        let rank = self.rank();
+        if rank == 1 {
+            return Some(Self::nullitope());
+        }
+
+        // The vertex becomes the minimal element of the vertex figure.
+        let mut builder = AbstractBuilder::with_rank_capacity(rank - 1);
+        builder.push_min();
+
+        // The elements of the previous rank containing the vertex, together
+        // with a map from their indices to their indices in the vertex figure.
+        let mut prev = vec![idx];
+        let mut prev_idx = HashMap::new();
+        prev_idx.insert(idx, 0);
+
+        for r in 2..rank {
+            let mut cur = Vec::new();
+            let mut cur_idx = HashMap::new();
+
+            for &el in &prev {
+                for &sup in &self[(r - 1, el)].sups {
+                    cur_idx.entry(sup).or_insert_with(|| {
+                        cur.push(sup);
+                        cur.len() - 1
+                    });
+                }
+            }
+
+            let mut subelements = SubelementList::with_capacity(cur.len());
+            for &el in &cur {
+                let mut subs: Vec<usize> = self[(r, el)]
+                    .subs
+                    .iter()
+                    .filter_map(|sub| prev_idx.get(sub).copied())
+                    .collect();
+                subs.sort_unstable();
+
+                subelements.push(subs.into());
+            }
+
+            builder.push(subelements);
+            prev = cur;
+            prev_idx = cur_idx;
+        }
+
+        builder.push_max();
+
+        // Safety: the elements containing a given vertex form a section of the
+        // polytope, which is itself a polytope.
+        Some(unsafe { builder.build() })
+    }
+
     /// Returns the omnitruncate of a polytope, along with the flags that make
     /// up its respective vertices.
     ///
@@ -460,7 +523,167 @@ impl Abstract {
         // write proof].
         (unsafe { builder.build() }, verts_subflags)
     }
-    
+
+    /// Builds the [alternate](https://polytope.miraheze.org/wiki/Alternation)
+    /// of a polytope by removing every other vertex, along with the indices of
+    /// the vertices that are kept. Returns `None` if the vertices can't be
+    /// split into two classes so that every edge joins both, or if the result
+    /// isn't a valid polytope.
+    ///
+    /// Every element of the alternate either comes from an element of the
+    /// original polytope restricted to the kept vertices, or from the
+    /// neighbors of a removed vertex within some element containing it. When
+    /// two of these share their vertices, only the one of lowest rank is kept,
+    /// so that for instance squares collapse into edges.
+    pub fn alternate_and_vertices(&self) -> Option<(Self, Vec<usize>)> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        // Colors the vertices so that every edge joins two different colors.
+        let vertex_count = self.vertex_count();
+        let mut color = vec![None; vertex_count];
+        for start in 0..vertex_count {
+            if color[start].is_some() {
+                continue;
+            }
+
+            color[start] = Some(true);
+            let mut stack = vec![(start, true)];
+            while let Some((v, c)) = stack.pop() {
+                for &edge in &self[(1, v)].sups {
+                    for &w in self[(2, edge)].subs.iter().filter(|&&w| w != v) {
+                        match color[w] {
+                            Some(d) if d == c => return None,
+                            Some(_) => {}
+                            None => {
+                                color[w] = Some(!c);
+                                stack.push((w, !c));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // The kept vertices, and a map from the old indices to the new ones.
+        let kept: Vec<usize> = (0..vertex_count)
+            .filter(|&v| color[v] == Some(true))
+            .collect();
+        let mut new_idx = vec![None; vertex_count];
+        for (i, &v) in kept.iter().enumerate() {
+            new_idx[v] = Some(i);
+        }
+
+        // The vertices of every element other than the maximal one.
+        let mut vertex_sets: Vec<Vec<_>> = vec![Vec::new(), (0..vertex_count).map(|v| vec![v]).collect()];
+        for r in 2..rank {
+            let sets: Vec<Vec<usize>> = self[r]
+                .iter()
+                .map(|el| {
+                    let mut set: Vec<usize> = el
+                        .subs
+                        .iter()
+                        .flat_map(|&sub| vertex_sets[r - 1][sub].iter().copied())
+                        .collect();
+                    set.sort_unstable();
+                    set.dedup();
+                    set
+                })
+                .collect();
+
+            vertex_sets.push(sets);
+        }
+
+        // Maps the vertex sets of the new elements to their ranks. An element
+        // of a given rank needs at least that many vertices.
+        let mut elements = BTreeMap::new();
+        let mut insert = |set: Vec<usize>, r: usize| {
+            if set.len() >= r {
+                let min_rank = elements.entry(set).or_insert(r);
+                *min_rank = r.min(*min_rank);
+            }
+        };
+
+        for (r, sets) in vertex_sets.iter().enumerate().skip(3) {
+            for set in sets {
+                insert(set.iter().filter_map(|&v| new_idx[v]).collect(), r);
+            }
+        }
+
+        for v in (0..vertex_count).filter(|&v| new_idx[v].is_none()) {
+            // The neighbors of the vertex within each element containing it.
+            let mut neighbors: HashMap<_, Vec<usize>> = HashMap::new();
+            let mut all = Vec::new();
+
+            for &edge in &self[(1, v)].sups {
+                let w = self[(2, edge)].subs.iter().find(|&&w| w != v)?;
+                let w = new_idx[*w]?;
+                all.push(w);
+
+                let mut prev = vec![edge];
+                for r in 3..rank {
+                    let mut cur = Vec::new();
+                    for &el in &prev {
+                        for &sup in &self[(r - 1, el)].sups {
+                            if !cur.contains(&sup) {
+                                cur.push(sup);
+                            }
+                        }
+                    }
+
+                    for &el in &cur {
+                        neighbors.entry((r, el)).or_default().push(w);
+                    }
+                    prev = cur;
+                }
+            }
+
+            for ((r, _), mut set) in neighbors.into_iter().chain(iter::once(((rank, 0), all))) {
+                set.sort_unstable();
+                set.dedup();
+                insert(set, r - 1);
+            }
+        }
+
+        let mut ranks = vec![Vec::new(); rank];
+        ranks[1] = (0..kept.len()).map(|v| vec![v]).collect();
+        for (set, r) in elements {
+            ranks[r].push(set);
+        }
+
+        // The subelements of each element are the elements of the previous
+        // rank whose vertices it contains.
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(kept.len());
+
+        for r in 2..rank {
+            let mut subelements = SubelementList::with_capacity(ranks[r].len());
+            for set in &ranks[r] {
+                let subs: Vec<usize> = ranks[r - 1]
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, sub)| sub.iter().all(|v| set.binary_search(v).is_ok()))
+                    .map(|(i, _)| i)
+                    .collect();
+
+                subelements.push(subs.into());
+            }
+
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+
+        // Safety: this construction doesn't always yield a valid polytope, so
+        // we check the result before returning it.
+        let alternate = unsafe { builder.build() };
+        alternate.ranks().is_valid().ok()?;
+        Some((alternate, kept))
+    }
+
     /// Returns whether a polytope is compound
     ///
     /// # Panics
@@ -701,7 +924,33 @@ impl Polytope for Abstract {
 
     /// Returns the flag omnitruncate of a polytope.
     fn omnitruncate(&self) -> Self {
-        self.omnitruncate_and_flags().0
+        let mut sorted = self.clone();
+        sorted.element_sort();
+        sorted.omnitruncate_and_flags().0
+    }
+
+    fn truncate(&self) -> Self {
+        if self.rank() < 3 {
+            return self.clone();
+        }
+
+        let mut sorted = self.clone();
+        sorted.element_sort();
+        sorted.truncate_and_flags(vec![0, 1]).0
+    }
+
+    fn rectify(&self) -> Self {
+        if self.rank() < 3 {
+            return self.clone();
+        }
+
+        let mut sorted = self.clone();
+        sorted.element_sort();
+        sorted.truncate_and_flags(vec![1]).0
+    }
+
+    fn alternate(&self) -> Option<Self> {
+        Some(self.alternate_and_vertices()?.0)
     }
 
     /// "Appends" a polytope into another, creating a compound polytope.
@@ -1014,6 +1263,31 @@ impl Polytope for Abstract {
         }
         self[3].append(&mut new_faces);
     }
+
+    /// Returns the skeleton of a polytope made out of its elements up to a
+    /// given rank, together with a new maximal element. Note that the result
+    /// needn't be a valid polytope.
+    fn skeleton(&self, rank: usize) -> Self {
+        if rank + 1 >= self.rank() {
+            return self.clone();
+        } else if rank == 0 {
+            return Self::nullitope();
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(self.vertex_count());
+
+        for r in 2..=rank {
+            builder.extend(self[r].iter().map(|el| el.subs.clone()));
+        }
+
+        builder.push_max();
+
+        // Safety: the result isn't a valid polytope in general, but it's still
+        // a well-formed ranked poset, which is what the methods on it rely on.
+        unsafe { builder.build() }
+    }
 }
 
 impl Index<(usize, usize)> for Abstract {
@@ -1111,6 +1385,22 @@ mod tests {
             .chain(std::iter::once(1))
     }
 
+    /// Checks the element counts of a cube.
+    #[test]
+    fn cube_counts() {
+        let cube = Abstract::cube();
+        assert_eq!(cube.el_counts(), vec![1, 8, 12, 6, 1]);
+        assert_eq!(cube.element_count(), 28);
+        assert_eq!(cube.element_iter().count(), cube.element_count());
+
+        // The element counts of the cube and the octahedron are reversed.
+        let octahedron = Abstract::octahedron();
+        assert!(cube
+            .el_count_iter()
+            .zip(octahedron.el_count_iter().rev())
+            .all(|(a, b)| a == b));
+    }
+
     /// Checks hypercubes.
     #[test]
     fn hypercube() {
@@ -1133,6 +1423,17 @@ mod tests {
         }
     }
 
+    /// Builds a cube as a product of dyads and counts its flags, without
+    /// using any geometric data.
+    #[test]
+    fn cube_flags() {
+        let mut cube = Abstract::dyad().prism().prism();
+        cube.element_sort();
+
+        test(&cube, [1, 8, 12, 6, 1]);
+        assert_eq!(cube.flags().count(), 48);
+    }
+
     /// Tests a few duals.
     #[test]
     fn dual() {
@@ -1140,4 +1441,112 @@ mod tests {
         test(&Abstract::polygon(6).into_dual(), [1, 6, 6, 1]);
         test(&Abstract::cube().into_dual(), [1, 6, 12, 8, 1]);
     }
+
+    /// Counts the components of a few polytopes and compounds.
+    #[test]
+    fn component_count() {
+        assert_eq!(Abstract::cube().component_count(), 1);
+        assert_eq!(Abstract::polygon(5).component_count(), 1);
+
+        let tetrahedra = Abstract::compound(iter::repeat_n(Abstract::tetrahedron(), 2));
+        assert_eq!(tetrahedra.component_count(), 2);
+
+        let triangles = Abstract::compound(iter::repeat_n(Abstract::polygon(3), 3));
+        assert_eq!(triangles.component_count(), 3);
+    }
+
+    /// Checks that vertex figures built directly match those built through
+    /// duals.
+    #[test]
+    fn vertex_figure() {
+        let cube = Abstract::cube();
+        let verf = cube.vertex_figure(0).unwrap();
+        test(&verf, [1, 3, 3, 1]);
+        assert!(verf.is_isomorphic(&cube.verf(0).unwrap().unwrap()));
+
+        test(&Abstract::polygon(5).vertex_figure(2).unwrap(), [1, 2, 1]);
+        test(&Abstract::simplex(5).vertex_figure(1).unwrap(), [1, 4, 6, 4, 1]);
+        test(&Abstract::dyad().vertex_figure(0).unwrap(), [1, 1]);
+        test(&Abstract::point().vertex_figure(0).unwrap(), [1]);
+        assert!(cube.vertex_figure(8).is_none());
+    }
+
+    /// Checks the skeleta of a cube.
+    #[test]
+    fn skeleton() {
+        let cube = Abstract::cube();
+        let edges = cube.skeleton(2);
+        assert_eq!(edges.rank(), 3);
+        assert_eq!(edges.vertex_count(), 8);
+        assert_eq!(edges.edge_count(), 12);
+        assert_eq!(edges.el_count(3), 1);
+
+        let vertices = cube.skeleton(1);
+        assert_eq!(vertices.rank(), 2);
+        assert_eq!(vertices.vertex_count(), 8);
+        test(&cube.skeleton(3), [1, 8, 12, 6, 1]);
+        test(&cube.skeleton(0), [1]);
+    }
+
+    /// Checks the Hasse diagram of a triangle.
+    #[test]
+    fn to_dot() {
+        let dot = Abstract::polygon(3).to_dot();
+        assert!(dot.starts_with("digraph {"));
+        assert_eq!(dot.matches("[label").count(), 8);
+        assert_eq!(dot.matches("->").count(), 12);
+        assert!(dot.contains("e1_0 -> e2_"));
+        assert!(dot.contains("[label = \"Vertices 2\"]"));
+    }
+
+    /// Omnitruncates a few polytopes.
+    #[test]
+    fn omnitruncate() {
+        test(&Abstract::polygon(5).omnitruncate(), [1, 10, 10, 1]);
+        test(&Abstract::cube().omnitruncate(), [1, 48, 72, 26, 1]);
+        test(&Abstract::tetrahedron().omnitruncate(), [1, 24, 36, 14, 1]);
+    }
+
+    /// Truncates a few polytopes.
+    #[test]
+    fn truncate() {
+        test(&Abstract::dyad().truncate(), [1, 2, 1]);
+        test(&Abstract::polygon(5).truncate(), [1, 10, 10, 1]);
+        test(&Abstract::cube().truncate(), [1, 24, 36, 14, 1]);
+        test(&Abstract::tetrahedron().truncate(), [1, 12, 18, 8, 1]);
+    }
+
+    /// Builds a few Petrie polygons.
+    #[test]
+    fn petrie_polygon() {
+        test(&Abstract::cube().petrie_polygon().unwrap(), [1, 6, 6, 1]);
+        test(&Abstract::tetrahedron().petrie_polygon().unwrap(), [1, 4, 4, 1]);
+        test(&Abstract::polygon(7).petrie_polygon().unwrap(), [1, 7, 7, 1]);
+    }
+
+    /// Rectifies a few polytopes.
+    #[test]
+    fn rectify() {
+        test(&Abstract::polygon(5).rectify(), [1, 5, 5, 1]);
+        test(&Abstract::cube().rectify(), [1, 12, 24, 14, 1]);
+        test(&Abstract::tetrahedron().rectify(), [1, 6, 12, 8, 1]);
+    }
+
+    /// Alternates a few polytopes.
+    #[test]
+    fn alternate() {
+        test(&Abstract::polygon(6).alternate().unwrap(), [1, 3, 3, 1]);
+        test(&Abstract::cube().alternate().unwrap(), [1, 4, 6, 4, 1]);
+        test(&Abstract::hypercube(5).alternate().unwrap(), orthoplex_counts(5));
+        assert!(Abstract::polygon(5).alternate().is_none());
+        assert!(Abstract::tetrahedron().alternate().is_none());
+    }
+
+    /// Snubs a few polytopes.
+    #[test]
+    fn snub() {
+        test(&Abstract::polygon(4).snub().unwrap(), [1, 4, 4, 1]);
+        test(&Abstract::cube().snub().unwrap(), [1, 24, 60, 38, 1]);
+        test(&Abstract::tetrahedron().snub().unwrap(), [1, 12, 30, 20, 1]);
+    }
 }
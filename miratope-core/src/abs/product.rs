@@ -223,14 +223,32 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
 /// The vertices of the result will be those corresponding to the vertices of
 /// `p` in the same order, following those corresponding to `q` in the same
 /// order.
+///
+/// The nullitope is the identity for this product, so in this case we just
+/// return a copy of the other factor.
 pub(super) fn duopyramid(p: &Abstract, q: &Abstract) -> Abstract {
-    product::<false, false>(q, p)
+    if p.is_nullitope() {
+        q.clone()
+    } else if q.is_nullitope() {
+        p.clone()
+    } else {
+        product::<false, false>(q, p)
+    }
 }
 
 /// Builds a [duoprism](https://polytope.miraheze.org/wiki/Prism_product)
 /// from two polytopes. This is a [`product`] where `MIN` and `!MAX`.
+///
+/// The point is the identity for this product, so in this case we just return
+/// a copy of the other factor.
 pub(super) fn duoprism(p: &Abstract, q: &Abstract) -> Abstract {
-    product::<true, false>(p, q)
+    if p.rank() == 1 {
+        q.clone()
+    } else if q.rank() == 1 {
+        p.clone()
+    } else {
+        product::<true, false>(p, q)
+    }
 }
 
 /// Builds a [duotegum](https://polytope.miraheze.org/wiki/Tegum_product)
@@ -239,8 +257,17 @@ pub(super) fn duoprism(p: &Abstract, q: &Abstract) -> Abstract {
 /// The vertices of the result will be those corresponding to the vertices of
 /// `p` in the same order, following those corresponding to `q` in the same
 /// order.
+///
+/// The point is the identity for this product, so in this case we just return
+/// a copy of the other factor.
 pub(super) fn duotegum(p: &Abstract, q: &Abstract) -> Abstract {
-    product::<false, true>(q, p)
+    if p.rank() == 1 {
+        q.clone()
+    } else if q.rank() == 1 {
+        p.clone()
+    } else {
+        product::<false, true>(q, p)
+    }
 }
 
 /// Builds a [duocomb](https://polytope.miraheze.org/wiki/Honeycomb_product)
@@ -266,6 +293,23 @@ mod tests {
         assert!(nullitope.duocomb(&cube).is_nullitope());
     }
 
+    /// Checks that the nullitope is the identity for the pyramid product, and
+    /// that the point is the identity for the prism and tegum products.
+    #[test]
+    fn identities() {
+        let nullitope = Abstract::nullitope();
+        let point = Abstract::point();
+
+        for p in [Abstract::dyad(), Abstract::polygon(5), Abstract::cube()] {
+            assert_eq!(nullitope.duopyramid(&p).ranks(), p.ranks());
+            assert_eq!(p.duopyramid(&nullitope).ranks(), p.ranks());
+            assert_eq!(point.duoprism(&p).ranks(), p.ranks());
+            assert_eq!(p.duoprism(&point).ranks(), p.ranks());
+            assert_eq!(point.duotegum(&p).ranks(), p.ranks());
+            assert_eq!(p.duotegum(&point).ranks(), p.ranks());
+        }
+    }
+
     /// Tests that polygonal duoproducts (i.e. duopyramids, duoprisms,
     /// duotegums, duocombs) are generated correctly by checking that the
     /// element counts for the product of an m-gon and an n-gon match for a few
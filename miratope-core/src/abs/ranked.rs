@@ -349,6 +349,18 @@ pub trait Ranked:
         self.ranks().iter().map(ElementList::len as LenFn)
     }
 
+    /// Returns the element counts of the structure, from the minimal element
+    /// up to the maximal element.
+    fn el_counts(&self) -> Vec<usize> {
+        self.el_count_iter().collect()
+    }
+
+    /// Returns the total number of elements of the structure, including the
+    /// minimal and maximal elements.
+    fn element_count(&self) -> usize {
+        self.el_count_iter().sum()
+    }
+
     /// Returns a reference to an element of the polytope. To actually get the
     /// entire polytope it defines, use [`Polytope::element`](crate::Polytope::element).
     fn get_element(&self, rank: usize, idx: usize) -> Option<&Element> {
@@ -1,192 +1,377 @@
-use std::collections::BTreeSet;
-use std::f64::NEG_INFINITY;
+//! Computes the [convex hulls](https://polytope.miraheze.org/wiki/Convex_hull)
+//! of sets of points.
+
+use std::collections::{BTreeSet, HashMap};
 
 use super::Concrete;
-use petgraph::{graph::NodeIndex, Directed, Direction, Graph};
-
-/// An entry in the priority queue used in Shell.
-enum QueueEntry<'a> {
-    /// Represents the event where at a certain time, the first facet that
-    /// contains a certain point becomes visible. This facet will have the
-    /// specified vertices and normal vector.
-    Point {
-        time: f64,
-        normal: Vector,
-        point: Point,
-        vertices: Vec<Point>,
-    },
-
-    /// Represents the event where at a certain time, a facet containing a
-    /// horizon peak and the horizon ridges specified by an element's neighbors
-    /// becomes visible. This facet will have the specified normal vector.
-    Peak {
-        time: f64,
-        normal: Vector,
-        element: ShellElement<'a>,
-    },
+use crate::{
+    abs::{Abstract, AbstractBuilder, SubelementList},
+    float::Float,
+    geometry::{Matrix, Point, PointOrd, Subspace, Vector},
+    Polytope,
+};
+
+use vec_like::*;
+
+/// A facet of the simplicial hull built by the beneath-beyond algorithm.
+struct SimplexFacet {
+    /// The indices of the vertices of the facet, in increasing order.
+    vertices: Vec<usize>,
+
+    /// The outer unit normal of the facet.
+    normal: Vector<f64>,
+
+    /// The signed distance from the origin to the hyperplane of the facet.
+    offset: f64,
 }
 
-impl<'a> QueueEntry<'a> {
-    /// Returns the time associated with an event.
-    pub fn time(&self) -> f64 {
-        match self {
-            QueueEntry::Point { time: t, .. } => *t,
-            QueueEntry::Peak { time: t, .. } => *t,
+impl SimplexFacet {
+    /// Builds the facet with the given vertices, oriented away from a point in
+    /// the interior of the hull.
+    fn new(mut vertices: Vec<usize>, points: &[Point<f64>], interior: &Point<f64>) -> Self {
+        vertices.sort_unstable();
+
+        let dim = interior.len();
+        let p0 = &points[vertices[0]];
+        let edges = Matrix::from_fn(dim - 1, dim, |i, j| points[vertices[i + 1]][j] - p0[j]);
+
+        // The generalized cross product of the edges from the first vertex.
+        let mut normal = Vector::from_fn(dim, |j, _| {
+            let det = edges.clone().remove_column(j).determinant();
+            if j % 2 == 0 {
+                det
+            } else {
+                -det
+            }
+        });
+        normal.normalize_mut();
+
+        let mut offset = normal.dot(p0);
+        if normal.dot(interior) > offset {
+            normal = -normal;
+            offset = -offset;
+        }
+
+        Self {
+            vertices,
+            normal,
+            offset,
         }
     }
-}
 
-impl<'a> PartialEq for QueueEntry<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.time() == other.time()
+    /// Returns the signed distance from a point to the hyperplane of the facet,
+    /// which is positive when the point lies beyond it.
+    fn distance(&self, p: &Point<f64>) -> f64 {
+        self.normal.dot(p) - self.offset
     }
 }
 
-impl<'a> Eq for QueueEntry<'a> {}
+/// Computes the hyperplanes of the facets of the convex hull of a set of
+/// points spanning the whole space, via the beneath-beyond algorithm. Every
+/// hyperplane is returned as its outer unit normal with its offset appended.
+fn hull_hyperplanes(points: &[Point<f64>]) -> Vec<Vector<f64>> {
+    let dim = points[0].len();
 
-impl<'a> PartialOrd for QueueEntry<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.time().partial_cmp(&other.time())
-    }
-}
+    // Finds a simplex spanning the whole space.
+    let mut subspace = Subspace::new(points[0].clone());
+    let mut simplex = vec![0];
+    for (idx, p) in points.iter().enumerate().skip(1) {
+        if simplex.len() == dim + 1 {
+            break;
+        }
 
-impl<'a> Ord for QueueEntry<'a> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        if subspace.add(p).is_some() {
+            simplex.push(idx);
+        }
     }
-}
 
-/// The metadata returned after deleting an element from a [`ShellQueue`]. This
-/// data specifies the minimum time in the queue, the union of all
-/// [`Point`](ShellQueueEntry::Point) and [`Peak`](ShellQueueEntry::Peak)
-/// entries at this time, and their common normal vector.
-struct QueueData<'a> {
-    time: f64,
-    normal: Vector,
-    vertices: Vec<Point>,
-    elements: Vec<ShellElement<'a>>,
-    points: Vec<Point>,
-}
+    let interior = simplex.iter().map(|&i| &points[i]).sum::<Point<f64>>() / f64::usize(dim + 1);
+    let mut facets: Vec<_> = (0..=dim)
+        .map(|i| {
+            let mut vertices = simplex.clone();
+            vertices.remove(i);
+            SimplexFacet::new(vertices, points, &interior)
+        })
+        .collect();
 
-#[derive(Deref, DerefMut)]
-struct Queue<'a>(BTreeSet<QueueEntry<'a>>);
+    // Adds the points one at a time, replacing the facets they can see by
+    // the cone from the point to the horizon.
+    for (idx, p) in points.iter().enumerate() {
+        if simplex.contains(&idx) {
+            continue;
+        }
 
-impl<'a> Queue<'a> {
-    pub fn new() -> Self {
-        Self(BTreeSet::new())
-    }
+        let (visible, hidden): (Vec<_>, Vec<_>) = facets
+            .into_iter()
+            .partition(|facet| facet.distance(p) > f64::EPS);
+        facets = hidden;
 
-    pub fn delete_min() -> QueueData<'a> {
-        todo!()
+        // The horizon consists of the ridges of exactly one visible facet.
+        let mut ridges = HashMap::new();
+        for facet in &visible {
+            for i in 0..facet.vertices.len() {
+                let mut ridge = facet.vertices.clone();
+                ridge.remove(i);
+                *ridges.entry(ridge).or_insert(0) += 1;
+            }
+        }
+
+        for (mut ridge, count) in ridges {
+            if count == 1 {
+                ridge.push(idx);
+                facets.push(SimplexFacet::new(ridge, points, &interior));
+            }
+        }
     }
-}
 
-/// An element produced by the Shell algorithm. Note that the data it contains
-/// is quite different from that of a [`Element`](super::Element).
-struct ShellElement<'a> {
-    normal: Vector,
-    neighbors: Vec<NodeIndex>,
-    queue: &'a Queue<'a>,
+    // Merges the simplices that lie on the same hyperplane.
+    facets
+        .into_iter()
+        .map(|facet| PointOrd::new(facet.normal.push(facet.offset)))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|hyperplane| hyperplane.0)
+        .collect()
 }
 
-struct ShellEdge<'a>(&'a Point);
-
-struct Line(Vector, Vector);
+/// Returns the facets of a face of a convex polytope, given the sorted vertex
+/// lists of the face and of all facets of the polytope. These are precisely
+/// the maximal proper nonempty intersections of the face with the facets.
+fn face_facets(face: &[usize], facets: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut intersections: Vec<Vec<usize>> = facets
+        .iter()
+        .map(|facet| {
+            face.iter()
+                .copied()
+                .filter(|v| facet.binary_search(v).is_ok())
+                .collect::<Vec<_>>()
+        })
+        .filter(|int| !int.is_empty() && int.len() < face.len())
+        .collect();
+    intersections.sort_unstable();
+    intersections.dedup();
 
-struct ShellPolytope<'a> {
-    dim: usize,
-    graph: Graph<ShellElement<'a>, ShellEdge<'a>, Directed>,
+    intersections
+        .iter()
+        .filter(|a| {
+            !intersections
+                .iter()
+                .any(|b| b.len() > a.len() && a.iter().all(|v| b.binary_search(v).is_ok()))
+        })
+        .cloned()
+        .collect()
 }
 
-impl<'a> ShellPolytope<'a> {
-    fn new(dim: usize) -> Self {
-        todo!()
-    }
+impl Concrete {
+    /// Builds the convex hull of a set of points, or returns `None` if the set
+    /// is empty.
+    ///
+    /// The rank of the hull is one more than the dimension of the affine hull
+    /// of the points, so that for instance the convex hull of a set of
+    /// collinear points is a dyad. Points that don't end up as vertices of the
+    /// hull are discarded.
+    pub fn convex_hull(points: &[Point<f64>]) -> Option<Self> {
+        // Removes duplicate points.
+        let points: Vec<_> = points
+            .iter()
+            .cloned()
+            .map(PointOrd::new)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|p| p.0)
+            .collect();
 
-    fn convex_hull(vertices: Vec<Point>) -> Concrete {
-        let s = Subspace::from_points(&vertices);
+        if points.is_empty() {
+            return None;
+        }
 
-        // A vector that is contained in s, but is in "general position."
-        let y = 0.57 * &s.basis[0] + 0.43 * &s.basis[1];
-        let a = s.orthogonal_comp();
-        let x: Point = vertices.iter().sum::<Point>() / vertices.len() as f64;
+        let subspace = Subspace::from_points(points.iter());
+        let flat_points = subspace.flatten_vec(&points);
+        let dim = subspace.rank();
 
-        let vertices = vertices.iter().collect::<Vec<_>>();
+        if dim == 0 {
+            return Some(Self::new(vec![points[0].clone()], Abstract::point()));
+        }
 
-        let mut poly = Self::new(vertices[0].nrows());
+        let hyperplanes = hull_hyperplanes(&flat_points);
+        let on_hyperplane = |hyperplane: &Vector<f64>, p: &Point<f64>| {
+            (hyperplane.rows(0, dim).dot(p) - hyperplane[dim]).abs() < f64::EPS
+        };
 
-        poly.shell(
-            Line(y, x),
-            NEG_INFINITY,
-            Vec::new(),
-            Vec::new(),
-            vertices.clone(),
-            vertices,
-            a,
-        );
+        // A point is a vertex when the normals of the facets through it span
+        // the whole space.
+        let mut vertices = Vec::new();
+        for (idx, p) in flat_points.iter().enumerate() {
+            let normals: Vec<Vector<f64>> = hyperplanes
+                .iter()
+                .filter(|hyperplane| on_hyperplane(hyperplane, p))
+                .map(|hyperplane| hyperplane.rows(0, dim).into_owned())
+                .collect();
 
-        poly.into()
-    }
+            if !normals.is_empty() && Matrix::from_columns(&normals).rank(f64::EPS) == dim {
+                vertices.push(idx);
+            }
+        }
 
-    fn shell(
-        &mut self,
-        line: Line,
-        time: f64,
-        ff: Vec<NodeIndex>,
-        hr: Vec<NodeIndex>,
-        u: Vec<&'a Point>,
-        t: Vec<&'a Point>,
-        n: Vec<Vector>,
-    ) -> ShellElement<'a> {
-        // Step 1
-        let q = self.graph.add_node(ShellElement {
-            normal: vec![].into(),
-            neighbors: Vec::new(),
-            queue: &Queue::new(),
-        });
+        // The indices of the vertices on each facet.
+        let facets: Vec<Vec<usize>> = hyperplanes
+            .iter()
+            .map(|hyperplane| {
+                (0..vertices.len())
+                    .filter(|&v| on_hyperplane(hyperplane, &flat_points[vertices[v]]))
+                    .collect()
+            })
+            .collect();
 
-        for f in ff {
-            self.graph.add_edge(f, q, weight);
-        }
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 2);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        // Finds the faces of each rank from the facets downwards, together
+        // with their subelements.
+        let mut ranks = Vec::with_capacity(dim);
+        let mut faces = facets.clone();
 
-        // Step 2
-        if t.len() == 1 {
-            let p = t[0];
-
-            self.graph.add_edge(
-                q,
-                if let Some(&e) = ff.get(0) {
-                    e
-                } else {
-                    self.graph.add_node(ShellElement {
-                        normal: Vector::zeros(self.dim),
-                        neighbors: Vec::new(),
+        for k in (1..dim).rev() {
+            let mut subfaces = Vec::new();
+            let mut subface_idx = HashMap::new();
+            let mut subelements = SubelementList::with_capacity(faces.len());
+
+            for face in &faces {
+                let mut subs: Vec<usize> = face_facets(face, &facets)
+                    .into_iter()
+                    .map(|subface| {
+                        // The facets of edges are vertices.
+                        if k == 1 {
+                            subface[0]
+                        } else {
+                            *subface_idx.entry(subface.clone()).or_insert_with(|| {
+                                subfaces.push(subface);
+                                subfaces.len() - 1
+                            })
+                        }
                     })
-                },
-                ShellEdge(&p),
-            );
-        }
+                    .collect();
+                subs.sort_unstable();
 
-        // Step 3
-        let mut hp = Vec::new();
-        for f in hr {
-            for g in self.graph.neighbors_directed(f, Direction::Outgoing) {
-                hp.push(g);
-                self.graph[g].neighbors.push(f);
+                subelements.push(subs.into());
             }
+
+            ranks.push(subelements);
+            faces = subfaces;
+        }
+
+        for subelements in ranks.into_iter().rev() {
+            builder.push(subelements);
         }
-        todo!()
+        builder.push_max();
+
+        // Safety: the faces of a convex polytope are determined by their
+        // vertices, and the facets of any face are its maximal intersections
+        // with the facets of the polytope.
+        let abs = unsafe { builder.build() };
+
+        Some(Self::new(
+            vertices.into_iter().map(|v| points[v].clone()).collect(),
+            abs,
+        ))
     }
 }
 
-impl<'a> Into<Concrete> for ShellPolytope<'a> {
-    fn into(self) -> Concrete {
-        todo!()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs::Ranked;
+
+    /// Checks that the convex hull of a set of points has the expected element
+    /// counts.
+    fn test_hull(points: &[Point<f64>], element_counts: &[usize]) -> Concrete {
+        let hull = Concrete::convex_hull(points).expect("Convex hull could not be built.");
+        crate::test(&hull, element_counts.iter().copied());
+        hull
     }
-}
 
-impl Concrete {
-    pub fn convex_hull_plus(&self) -> Concrete {
-        convex_hull(self.vertices.clone())
+    #[test]
+    fn empty() {
+        assert!(Concrete::convex_hull(&[]).is_none());
+    }
+
+    #[test]
+    fn point() {
+        let p: Point<f64> = vec![1.0, 2.0].into();
+        let hull = test_hull(&[p.clone(), p.clone()], &[1, 1]);
+        assert_eq!(hull.vertices, vec![p]);
+    }
+
+    /// Checks that the hull of collinear points is a dyad.
+    #[test]
+    fn collinear() {
+        let points: Vec<Point<f64>> = (0..5).map(|i| vec![i as f64, 2.0 * i as f64].into()).collect();
+        let hull = test_hull(&points, &[1, 2, 1]);
+        assert!(hull.vertices.contains(&points[0]));
+        assert!(hull.vertices.contains(&points[4]));
+    }
+
+    #[test]
+    fn cube() {
+        test_hull(&Concrete::cube().vertices, &[1, 8, 12, 6, 1]);
+    }
+
+    /// Checks that points in the interior or on the boundary of the hull
+    /// aren't taken as vertices.
+    #[test]
+    fn cube_extra_points() {
+        let mut points = Concrete::cube().vertices;
+        points.push(vec![0.0, 0.0, 0.0].into());
+        points.push(vec![0.0, 0.0, 0.5].into());
+        points.push(vec![0.5, 0.5, 0.0].into());
+        points.push(vec![0.1, -0.2, 0.3].into());
+
+        test_hull(&points, &[1, 8, 12, 6, 1]);
+    }
+
+    /// Checks that a square in 3D space gets built as a square.
+    #[test]
+    fn square() {
+        let points: Vec<Point<f64>> = Concrete::polygon(4)
+            .vertices
+            .iter()
+            .map(|v| vec![v[0], v[1], v[0]].into())
+            .collect();
+
+        test_hull(&points, &[1, 4, 4, 1]);
+    }
+
+    #[test]
+    fn tesseract() {
+        test_hull(&Concrete::hypercube(5).vertices, &[1, 16, 32, 24, 8, 1]);
+    }
+
+    /// Checks that the hull of points on a sphere is a valid closed
+    /// polyhedron with all of the points as vertices.
+    #[test]
+    fn sphere() {
+        const N: usize = 30;
+
+        // Distributes points over the sphere in a Fibonacci spiral.
+        let angle = f64::PI * (3.0 - f64::SQRT_5);
+        let points: Vec<Point<f64>> = (0..N)
+            .map(|i| {
+                let z = 1.0 - 2.0 * (i as f64 + 0.5) / N as f64;
+                let r = (1.0 - z * z).fsqrt();
+                let (sin, cos) = (angle * i as f64).fsin_cos();
+                vec![r * cos, r * sin, z].into()
+            })
+            .collect();
+
+        let hull = Concrete::convex_hull(&points).unwrap();
+        hull.assert_valid();
+        assert_eq!(hull.vertex_count(), N);
+
+        // Euler's formula.
+        assert_eq!(
+            hull.vertex_count() + hull.el_count(3),
+            hull.edge_count() + 2
+        );
     }
 }
@@ -2216,4 +2216,45 @@ impl Concrete {
             return output
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cox::cd::Cd;
+
+    /// Checks that the great dodecahedron is found among the facetings of the
+    /// icosahedron.
+    #[test]
+    fn icosahedron() {
+        let mut icosahedron = Cd::parse("x3o5o").unwrap().build().unwrap();
+        let vertices = icosahedron.vertices.clone();
+
+        let facetings = icosahedron.faceting(
+            vertices,
+            GroupEnum::Chiral(false),
+            false,         // any_single_edge_length
+            None,          // min_edge_length
+            None,          // max_edge_length
+            None,          // min_inradius
+            None,          // max_inradius
+            false,         // exclude_hemis
+            false,         // only_below_vertex
+            None,          // noble
+            None,          // max_per_hyperplane
+            false,         // uniform
+            false,         // include_compounds
+            false,         // mark_fissary
+            false,         // label_facets
+            true,          // save
+            false,         // save_facets
+            false,         // save_to_file
+            String::new(), // file_path
+        );
+
+        assert!(facetings.iter().any(|(poly, _)| {
+            poly.el_count_iter().eq([1, 12, 30, 12, 1].iter().copied())
+                && poly.is_equilateral_with(1.0)
+        }));
+    }
+}
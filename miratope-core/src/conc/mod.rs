@@ -1,5 +1,6 @@
 //! Declares the [`Concrete`] polytope type and all associated data structures.
 
+pub mod convex;
 pub mod cycle;
 pub mod element_types;
 pub mod faceting;
@@ -27,6 +28,104 @@ use partitions::{PartitionVec, partition_vec};
 use rayon::prelude::*;
 use vec_like::*;
 
+/// Represents an error in building a [`Concrete`] polytope out of a set of
+/// vertices and an underlying abstract polytope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConcreteError {
+    /// The number of vertices doesn't match the vertex count of the abstract
+    /// polytope.
+    VertexCount {
+        /// The vertex count of the abstract polytope.
+        expected: usize,
+
+        /// The number of vertices given.
+        found: usize,
+    },
+
+    /// Some vertex doesn't have the same dimension as the first.
+    Dimension {
+        /// The index of the vertex at fault.
+        idx: usize,
+
+        /// The dimension of the first vertex.
+        expected: usize,
+
+        /// The dimension of the vertex at fault.
+        found: usize,
+    },
+}
+
+impl std::fmt::Display for ConcreteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VertexCount { expected, found } => write!(
+                f,
+                "expected {} vertices, found {}",
+                expected, found
+            ),
+
+            Self::Dimension {
+                idx,
+                expected,
+                found,
+            } => write!(
+                f,
+                "vertex {} has dimension {}, expected {}",
+                idx, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConcreteError {}
+
+/// Represents an error in building an antiprism with a given reciprocation
+/// sphere.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AntiprismError {
+    /// The reciprocation sphere has zero radius.
+    DegenerateSphere,
+
+    /// The facet with a given index passes through the reciprocation center.
+    FacetThroughCenter(usize),
+
+    /// The center of the reciprocation sphere doesn't have the same dimension
+    /// as the polytope.
+    WrongDimension {
+        /// The dimension of the polytope.
+        expected: usize,
+
+        /// The dimension of the center of the sphere.
+        found: usize,
+    },
+}
+
+impl From<DualError> for AntiprismError {
+    fn from(err: DualError) -> Self {
+        Self::FacetThroughCenter(err.0)
+    }
+}
+
+impl std::fmt::Display for AntiprismError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DegenerateSphere => write!(f, "reciprocation sphere has zero radius"),
+
+            Self::FacetThroughCenter(idx) => {
+                write!(f, "facet {} passes through reciprocation center", idx)
+            }
+
+            Self::WrongDimension { expected, found } => write!(
+                f,
+                "reciprocation sphere has dimension {}, expected {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AntiprismError {}
+
 /// Represents a [concrete polytope](https://polytope.miraheze.org/wiki/Polytope),
 /// which is an [`Abstract`] together with its corresponding vertices.
 #[derive(Debug, Clone)]
@@ -95,6 +194,107 @@ impl Concrete {
         // With no further info, we create a generic name for the polytope.
         Self { vertices, abs }
     }
+
+    /// Initializes a new concrete polytope from a set of vertices and an
+    /// underlying abstract polytope. Unlike [`Self::new`], this checks that
+    /// there are as many vertices as in the abstract polytope, and that all of
+    /// them have the same dimension.
+    pub fn try_new(vertices: Vec<Point<f64>>, abs: Abstract) -> Result<Self, ConcreteError> {
+        if abs.vertex_count() != vertices.len() {
+            return Err(ConcreteError::VertexCount {
+                expected: abs.vertex_count(),
+                found: vertices.len(),
+            });
+        }
+
+        if let Some(vertex0) = vertices.first() {
+            for (idx, vertex) in vertices.iter().enumerate() {
+                if vertex.len() != vertex0.len() {
+                    return Err(ConcreteError::Dimension {
+                        idx,
+                        expected: vertex0.len(),
+                        found: vertex.len(),
+                    });
+                }
+            }
+        }
+
+        Ok(Self { vertices, abs })
+    }
+
+    /// Triangulates the 2-faces of a polytope. Returns the vertices of the
+    /// triangulation, starting with those of the polytope, together with the
+    /// indices of the vertices of each triangle. For polytopes of rank greater
+    /// than 3, the result can be projected down for rendering afterwards.
+    ///
+    /// Every cycle of a face is fan-triangulated: convex planar cycles from
+    /// their first vertex, and any other cycles from their centroid, which gets
+    /// added as a new vertex. Since the centroid lies on the best-fit plane of
+    /// a cycle, this also handles non-planar faces.
+    pub fn triangulate(&self) -> (Vec<Point<f64>>, Vec<[usize; 3]>) {
+        let mut vertices = self.vertices.clone();
+        let mut triangles = Vec::new();
+
+        if self.rank() < 3 {
+            return (vertices, triangles);
+        }
+
+        for face in &self[3] {
+            for cycle in cycle::CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs)) {
+                let n = cycle.len();
+                if n < 3 {
+                    continue;
+                }
+
+                let points: Vec<_> = cycle.iter().map(|&i| &self.vertices[i]).collect();
+                if is_convex_polygon(&points) {
+                    for i in 1..n - 1 {
+                        triangles.push([cycle[0], cycle[i], cycle[i + 1]]);
+                    }
+                } else {
+                    let centroid = points.into_iter().sum::<Point<f64>>() / f64::usize(n);
+                    let c = vertices.len();
+                    vertices.push(centroid);
+
+                    for i in 0..n {
+                        triangles.push([c, cycle[i], cycle[(i + 1) % n]]);
+                    }
+                }
+            }
+        }
+
+        (vertices, triangles)
+    }
+}
+
+/// Returns whether a polygon, given by its vertices in cyclic order, is planar
+/// and strictly convex. Polygons that wind around more than once, like star
+/// polygons, aren't considered convex.
+fn is_convex_polygon(points: &[&Point<f64>]) -> bool {
+    let subspace = Subspace::from_points(points.iter().copied());
+    if subspace.rank() != 2 {
+        return false;
+    }
+
+    let flat: Vec<_> = points.iter().map(|&p| subspace.flatten(p)).collect();
+    let n = flat.len();
+    let mut winding = 0.0;
+    let mut sign = 0.0;
+
+    for i in 0..n {
+        let u = &flat[(i + 1) % n] - &flat[i];
+        let v = &flat[(i + 2) % n] - &flat[(i + 1) % n];
+        let cross = u[0] * v[1] - u[1] * v[0];
+
+        if cross.abs() < f64::EPS || cross * sign < 0.0 {
+            return false;
+        }
+
+        sign = cross;
+        winding += cross.atan2(u.dot(&v));
+    }
+
+    (winding.abs() - f64::TAU).abs() < f64::EPS
 }
 
 impl Polytope for Concrete {
@@ -310,7 +510,9 @@ impl Polytope for Concrete {
     // TODO: A method that builds an omnitruncate together with a map from flags
     // to vertices? We got some math details to figure out.
     fn omnitruncate(&self) -> Self {
-        let (abs, flags) = self.abs.omnitruncate_and_flags();
+        let mut sorted = self.abs.clone();
+        sorted.element_sort();
+        let (abs, flags) = sorted.omnitruncate_and_flags();
         let element_vertices = self.avg_vertex_map();
 
         Self::new(
@@ -329,9 +531,58 @@ impl Polytope for Concrete {
         )
     }
 
+    /// Returns the truncate of a polytope, cutting every edge into thirds.
+    fn truncate(&self) -> Self {
+        let rank = self.rank();
+        if rank < 3 {
+            return self.clone();
+        }
+
+        let mut sorted = self.clone();
+        sorted.element_sort();
+
+        let mut depth = vec![0.0; rank - 1];
+        depth[0] = 1.0 / 3.0;
+        depth[1] = 2.0 / 3.0;
+        sorted.truncate_with(vec![0, 1], depth)
+    }
+
+    /// Returns the rectate of a polytope, placing its vertices at the edge
+    /// midpoints.
+    fn rectify(&self) -> Self {
+        let rank = self.rank();
+        if rank < 3 {
+            return self.clone();
+        }
+
+        let mut sorted = self.clone();
+        sorted.element_sort();
+
+        let mut depth = vec![0.0; rank - 1];
+        depth[1] = 1.0;
+        sorted.truncate_with(vec![1], depth)
+    }
+
+    fn alternate(&self) -> Option<Self> {
+        let (abs, kept) = self.abs.alternate_and_vertices()?;
+        Some(Self::new(
+            kept.into_iter().map(|v| self.vertices[v].clone()).collect(),
+            abs,
+        ))
+    }
+
     /// Builds a [duopyramid](https://polytope.miraheze.org/wiki/Pyramid_product)
     /// with unit height from two polytopes. Does not offset either polytope.
+    ///
+    /// The nullitope is the identity for this product, so in this case we just
+    /// return a copy of the other factor.
     fn duopyramid(&self, other: &Self) -> Self {
+        if self.is_nullitope() {
+            return other.clone();
+        } else if other.is_nullitope() {
+            return self.clone();
+        }
+
         self.duopyramid_with(
             other,
             &Point::zeros(self.dim_or()),
@@ -408,7 +659,11 @@ impl Polytope for Concrete {
     /// If you want more control over the arguments, you can use
     /// [`Self::try_antiprism_with`].
     fn try_antiprism(&self) -> Result<Self, Self::DualError> {
-        Self::try_antiprism_with(self, &Hypersphere::unit(self.dim().unwrap_or(1)), 1.0)
+        let dual = self.try_dual()?;
+        Ok(self.antiprism_with_vertices(
+            self.vertices.iter().map(|v| v.push(-0.5)),
+            dual.vertices.iter().map(|v| v.push(0.5)),
+        ))
     }
 
     /// Builds a [simplex](https://polytope.miraheze.org/wiki/Simplex) with a
@@ -453,6 +708,16 @@ impl Polytope for Concrete {
     fn untangle_faces(&mut self) {
         self.abs.untangle_faces();
     }
+
+    /// Returns the skeleton of a polytope up to a given rank, with the same
+    /// vertices as the original polytope.
+    fn skeleton(&self, rank: usize) -> Self {
+        if rank == 0 {
+            Self::nullitope()
+        } else {
+            Self::new(self.vertices.clone(), self.abs.skeleton(rank))
+        }
+    }
 }
 
 /// Generates the vertices for either a tegum or a pyramid product with two
@@ -637,9 +902,56 @@ pub trait ConcretePolytope: Polytope {
         self
     }
 
+    /// Orthogonally projects a polytope onto the subspace spanned by the rows
+    /// of a matrix, keeping its abstract structure. The rows are assumed to be
+    /// orthonormal.
+    ///
+    /// Vertices are padded with zeros or truncated so that their dimension
+    /// matches the number of columns of the matrix.
+    fn project(&self, basis: &Matrix<f64>) -> Self {
+        let dim = basis.ncols();
+        let mut poly = self.clone();
+
+        for v in poly.vertices_mut() {
+            let padded = v.clone().resize_vertically(dim, 0.0);
+            *v = basis * padded;
+        }
+
+        poly
+    }
+
+    /// Projects a polytope into 3D by keeping only its first three
+    /// coordinates. Polytopes in fewer dimensions are padded with zeros.
+    fn project_to_3d(&self) -> Self {
+        let dim = self.dim_or().max(3);
+        self.project(&Matrix::identity(3, dim))
+    }
+
     /// Returns an arbitrary truncate of a polytope.
     fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self;
 
+    /// Returns a truncate of a polytope whose vertices lie on its edges, at a
+    /// given depth. A depth of 0 places the vertices at the edge midpoints,
+    /// giving the [rectate](Polytope::rectify), and a depth of 1 places them at
+    /// the original vertices, giving back the polytope itself. In between, the
+    /// vertices are placed a fraction `(1 - depth) / 2` of the way along each
+    /// edge, so that a depth of 1/3 gives the [truncate](Polytope::truncate).
+    fn truncate_at(&self, depth: f64) -> Self {
+        if self.rank() < 3 || depth >= 1.0 {
+            return self.clone();
+        } else if depth <= 0.0 {
+            return self.rectify();
+        }
+
+        let mut sorted = self.clone();
+        sorted.element_sort();
+
+        let mut depths = vec![0.0; self.rank() - 1];
+        depths[0] = depth;
+        depths[1] = 1.0 - depth;
+        sorted.truncate_with(vec![0, 1], depths)
+    }
+
     /// Calculates the circumsphere of a polytope. Returns `None` if the
     /// polytope isn't circumscribable.
     fn circumsphere(&self) -> Option<Hypersphere<f64>> {
@@ -718,6 +1030,21 @@ pub trait ConcretePolytope: Polytope {
         Some((&self.vertices()[edge.subs[0]] - &self.vertices()[edge.subs[1]]).norm())
     }
 
+    /// Returns the lengths of all edges, in order.
+    fn edge_lengths(&self) -> Vec<f64> {
+        (0..self.edge_count())
+            .map(|idx| self.edge_len(idx).unwrap())
+            .collect()
+    }
+
+    /// Checks whether all edges of a polytope have unit length, up to a given
+    /// tolerance.
+    fn is_unit_edge(&self, tol: f64) -> bool {
+        self.edge_lengths()
+            .into_iter()
+            .all(|len| abs_diff_eq!(len, 1.0, epsilon = tol))
+    }
+
     /// Checks whether a polytope is equilateral to a fixed precision, and with
     /// a specified edge length.
     fn is_equilateral_with(&self, len: f64) -> bool {
@@ -730,6 +1057,103 @@ pub trait ConcretePolytope: Polytope {
         self.edge_count() == 0 || self.is_equilateral_with(self.edge_len(0).unwrap())
     }
 
+    /// Returns the dihedral angle at the ridge with a given index, i.e. the
+    /// angle between the two facets containing it, measured between the
+    /// directions within each facet perpendicular to the ridge. For convex
+    /// polytopes, this is the interior angle.
+    ///
+    /// Returns `None` if the ridge doesn't exist, if it isn't contained in
+    /// exactly two facets, or if one of these is degenerate.
+    fn dihedral_angle(&self, idx: usize) -> Option<f64> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        let ridge = self.get_element(rank - 2, idx)?;
+        if ridge.sups.len() != 2 {
+            return None;
+        }
+
+        let subspace = Subspace::from_points(self.element_vertices_ref(rank - 2, idx)?.into_iter());
+        let mut normals = ridge.sups.iter().map(|&facet| {
+            self.element_vertices_ref(rank - 1, facet)?
+                .into_iter()
+                .find_map(|v| subspace.normal(v))
+        });
+
+        let n0 = normals.next()??;
+        let n1 = normals.next()??;
+        Some(n0.dot(&n1).clamp(-1.0, 1.0).acos())
+    }
+
+    /// Returns the [dihedral angles](Self::dihedral_angle) at every ridge of
+    /// the polytope, in order. Polytopes of rank less than 3 have none.
+    fn dihedral_angles(&self) -> Vec<Option<f64>> {
+        if self.rank() < 3 {
+            return Vec::new();
+        }
+
+        let ridge_count = self.rank().checked_sub(2).map_or(0, |r| self.el_count(r));
+        (0..ridge_count).map(|idx| self.dihedral_angle(idx)).collect()
+    }
+
+    /// Checks whether a polytope is convex to a fixed precision. That is,
+    /// whether its vertices span a subspace of the expected dimension, and all
+    /// of them lie on the same side of the hyperplane through every facet.
+    ///
+    /// Polytopes that are degenerate, skew, or have degenerate facets, are
+    /// never considered convex.
+    fn is_convex(&self) -> bool {
+        // The nullitope and the point are trivially convex. The only facet of
+        // the point is the nullitope, which spans no subspace.
+        let rank = self.rank();
+        if rank <= 1 {
+            return true;
+        }
+
+        if Subspace::from_points(self.vertices().iter()).rank() + 1 != rank {
+            return false;
+        }
+
+        let facet_rank = rank - 1;
+        (0..self.facet_count()).all(|idx| {
+            let facet = self.element_vertices_ref(facet_rank, idx).unwrap();
+            let subspace = Subspace::from_points(facet.iter().copied());
+            if subspace.rank() + 2 != rank {
+                return false;
+            }
+
+            // A normal vector pointing towards any vertex off the facet.
+            let normal = match self.vertices().iter().find_map(|v| subspace.normal(v)) {
+                Some(normal) => normal,
+                None => return false,
+            };
+
+            self.vertices()
+                .iter()
+                .all(|v| normal.dot(&(v - facet[0])) > -f64::EPS)
+        })
+    }
+
+    /// Scales a polytope so that all of its edges have a given length. Does
+    /// nothing and returns an error if the polytope isn't equilateral, or if it
+    /// has no edges of nonzero length.
+    #[allow(clippy::result_unit_err)]
+    fn scale_to_edge_length(&mut self, target: f64) -> Result<(), ()> {
+        if !self.is_equilateral() {
+            return Err(());
+        }
+
+        match self.edge_len(0) {
+            Some(edge_len) if edge_len > f64::EPS => {
+                self.scale(target / edge_len);
+                Ok(())
+            }
+            _ => Err(()),
+        }
+    }
+
     /// I haven't actually implemented this in the general case.
     ///
     /// # Todo
@@ -739,11 +1163,61 @@ pub trait ConcretePolytope: Polytope {
         (&self.vertices()[edge_subs[0]] + &self.vertices()[edge_subs[1]]).norm() / 2.0
     }
 
+    /// Returns the circumradius of a polytope, i.e. the radius of the
+    /// [circumsphere](Self::circumsphere) through all of its vertices. Returns
+    /// `None` if the polytope isn't circumscribable.
+    fn circumradius(&self) -> Option<f64> {
+        if self.is_nullitope() {
+            return None;
+        }
+
+        self.circumsphere().map(|sphere| sphere.radius())
+    }
+
+    /// Returns the inradius of a polytope, i.e. the least distance from its
+    /// [centroid](Self::centroid) to the subspace spanned by any of its
+    /// facets. Returns `None` if the centroid is undefined, or if the polytope
+    /// has no facets with vertices, as for points.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn inradius(&self) -> Option<f64> {
+        let rank = self.rank();
+        if rank <= 1 {
+            return None;
+        }
+
+        let centroid = self.centroid()?;
+        let facet_rank = rank - 1;
+
+        (0..self.facet_count())
+            .map(|idx| {
+                let vertices = self.element_vertices_ref(facet_rank, idx).unwrap();
+                ordered_float::OrderedFloat(
+                    Subspace::from_points(vertices.into_iter()).distance(&centroid),
+                )
+            })
+            .min()
+            .map(|distance| distance.0)
+    }
+
     /// Builds the dual of a polytope with a given reciprocation sphere in
     /// place, or does nothing in case any facets go through the reciprocation
     /// center. In case of failure, returns the index of the facet through the
     /// projection center.
-    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError>;
+    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError> {
+        self.try_dual_mut_with_tol(sphere, f64::EPS.fsqrt())
+    }
+
+    /// Builds the dual of a polytope with a given reciprocation sphere in
+    /// place, considering a facet to pass through the reciprocation center
+    /// whenever it's closer to it than a given tolerance. In case of failure,
+    /// returns the index of such a facet and does nothing.
+    fn try_dual_mut_with_tol(
+        &mut self,
+        sphere: &Hypersphere<f64>,
+        tol: f64,
+    ) -> Result<(), Self::DualError>;
 
     /// Returns the dual of a polytope with a given reciprocation sphere, or
     /// `None` if any facets pass through the reciprocation center.
@@ -781,14 +1255,32 @@ pub trait ConcretePolytope: Polytope {
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
     /// based on a given polytope. Uses the specified [`Hypersphere`] to build
     /// the dual base, and separates the bases by the given height.
+    ///
+    /// Fails if the sphere is degenerate or of the wrong dimension, or if any
+    /// facet passes through its center.
     fn try_antiprism_with(
         &self,
         sphere: &Hypersphere<f64>,
         height: f64,
-    ) -> Result<Self, Self::DualError> {
+    ) -> Result<Self, AntiprismError> {
+        if let Some(dim) = self.dim() {
+            let found = sphere.center.len();
+            if found != dim {
+                return Err(AntiprismError::WrongDimension {
+                    expected: dim,
+                    found,
+                });
+            }
+        }
+
+        let squared_radius = sphere.squared_radius;
+        if squared_radius.abs() <= f64::EPS || squared_radius.is_nan() {
+            return Err(AntiprismError::DegenerateSphere);
+        }
+
         let half_height = height / 2.0;
         let vertices = self.vertices().iter().map(|v| v.push(-half_height));
-        let dual = self.try_dual_with(sphere)?;
+        let dual = self.con().try_dual_with(sphere)?;
         let dual_vertices = dual.vertices().iter().map(|v| v.push(half_height));
 
         Ok(self.antiprism_with_vertices(vertices, dual_vertices))
@@ -798,8 +1290,9 @@ pub trait ConcretePolytope: Polytope {
     /// with a given height.
     ///
     /// # Panics
-    /// Panics if any facets pass through the inversion center. If you want to
-    /// handle this possibility, use [`Self::try_antiprism_with`] instead.
+    /// Panics if any facets pass through the inversion center, or if the
+    /// sphere is invalid. If you want to handle these possibilities, use
+    /// [`Self::try_antiprism_with`] instead.
     fn antiprism_with(&self, sphere: &Hypersphere<f64>, height: f64) -> Self {
         self.try_antiprism_with(sphere, height).unwrap()
     }
@@ -863,12 +1356,13 @@ pub trait ConcretePolytope: Polytope {
     /// Generates a duopyramid from two given polytopes with a given offset.
     fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self;
 
-    /// Computes the volume of a polytope by adding up the contributions of all
-    /// flags. Returns `None` if the volume is undefined.
+    /// Computes the volume of a polytope together with its first moment, i.e.
+    /// the integral of the position vector over the polytope, by adding up the
+    /// contributions of all flags. Returns `None` if the volume is undefined.
     ///
     /// # Panics
     /// You must call [`Polytope::element_sort`] before calling this method.
-    fn volume(&self) -> Option<f64> {
+    fn volume_and_moment(&self) -> Option<(f64, Point<f64>)> {
         let rank = self.rank();
 
         // We leave the nullitope's volume undefined.
@@ -879,12 +1373,13 @@ pub trait ConcretePolytope: Polytope {
         // The flattened vertices (may possibly be the original vertices).
         let subspace = Subspace::from_points(self.vertices().iter());
         let flat_vertices = subspace.flatten_vec(self.vertices());
+        let dim = self.vertices()[0].len();
 
         match flat_vertices.get(0)?.len().cmp(&(rank - 1)) {
             // Degenerate polytopes have volume 0 (if they're orientable).
             std::cmp::Ordering::Less => {
                 if self.orientable() {
-                    return Some(0.0)
+                    return Some((0.0, Point::zeros(dim)))
                 } else {
                     return None
                 }
@@ -896,9 +1391,14 @@ pub trait ConcretePolytope: Polytope {
             _ => {}
         }
 
+        // Every flag defines a simplex with this common apex.
+        let apex = &self.vertices()[0];
+        let flat_apex = &flat_vertices[0];
+
         // Maps every element of the polytope to one of its vertices.
         let vertex_map = self.vertex_map();
         let mut volume = 0.0;
+        let mut moment = Point::zeros(dim);
 
         // All of the flags we've found so far.
         let mut all_flags = HashSet::new();
@@ -909,6 +1409,7 @@ pub trait ConcretePolytope: Polytope {
             // over the oriented flags in this component.
             if !all_flags.contains(&flag) {
                 let mut component_volume = 0.0;
+                let mut component_moment = Point::zeros(dim);
 
                 for flag_event in
                     OrientedFlagIter::with_flags(self.abs(), FlagChanges::all(rank), flag.into())
@@ -917,23 +1418,38 @@ pub trait ConcretePolytope: Polytope {
                         let new = all_flags.insert(oriented_flag.flag.clone());
                         debug_assert!(new, "A flag is in two different components.");
 
+                        let sign = oriented_flag.orientation.sign();
+                        let vertices: Vec<usize> = oriented_flag
+                            .into_iter()
+                            .enumerate()
+                            .skip(1)
+                            .take(rank - 1)
+                            .map(|(rank, idx)| vertex_map[(rank, idx)])
+                            .collect();
+
                         // For each flag, there's a simplex defined by any vertices in its
-                        // elements and the origin. We add up the volumes of all of these
+                        // elements and the apex. We add up the volumes of all of these
                         // simplices times the sign of the flag that generated them.
-                        component_volume += oriented_flag.orientation.sign()
+                        let simplex_volume = sign
                             * Matrix::from_iterator(
                                 rank - 1,
                                 rank - 1,
-                                oriented_flag
-                                    .into_iter()
-                                    .enumerate()
-                                    .skip(1)
-                                    .take(rank - 1)
-                                    .map(|(rank, idx)| &flat_vertices[vertex_map[(rank, idx)]])
-                                    .flatten()
-                                    .copied(),
+                                vertices
+                                    .iter()
+                                    .flat_map(|&v| (&flat_vertices[v] - flat_apex).iter().copied().collect::<Vec<_>>()),
                             )
                             .determinant();
+
+                        // The moment of a simplex is its volume times its
+                        // centroid.
+                        let simplex_centroid = vertices
+                            .iter()
+                            .map(|&v| &self.vertices()[v])
+                            .fold(apex.clone(), |sum, v| sum + v)
+                            / f64::usize(rank);
+
+                        component_volume += simplex_volume;
+                        component_moment += simplex_centroid * simplex_volume;
                     }
                     // A non-orientable polytope doesn't have a volume.
                     else {
@@ -941,12 +1457,40 @@ pub trait ConcretePolytope: Polytope {
                     }
                 }
 
-                // We add up the volumes of all components.
-                volume += component_volume.fabs();
+                // We add up the volumes of all components, making sure that
+                // all of them are positively oriented.
+                if component_volume < 0.0 {
+                    volume -= component_volume;
+                    moment -= component_moment;
+                } else {
+                    volume += component_volume;
+                    moment += component_moment;
+                }
             }
         }
 
-        Some(volume / f64::u32(crate::factorial(rank - 1)))
+        let factorial = f64::u32(crate::factorial(rank - 1));
+        Some((volume / factorial, moment / factorial))
+    }
+
+    /// Computes the volume of a polytope by adding up the contributions of all
+    /// flags. Returns `None` if the volume is undefined.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn volume(&self) -> Option<f64> {
+        self.volume_and_moment().map(|(volume, _)| volume)
+    }
+
+    /// Computes the centroid of a polytope, weighted by volume. Returns `None`
+    /// if the volume of the polytope is undefined or zero. See
+    /// [`Self::gravicenter`] for the average of the vertices.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn centroid(&self) -> Option<Point<f64>> {
+        let (volume, moment) = self.volume_and_moment()?;
+        (volume > f64::EPS).then(|| moment / volume)
     }
 
     /// Computes the volume of a polytope by adding up the contributions of all
@@ -963,8 +1507,9 @@ pub trait ConcretePolytope: Polytope {
     /// Flattens the vertices of a polytope into a specified subspace.
     fn flatten_into(&mut self, subspace: &Subspace<f64>);
 
-    /// Slices the polytope through a given plane.
-    fn cross_section(&self, slice: &Hyperplane<f64>) -> Self;
+    /// Slices the polytope through a given plane. Returns `None` if the plane
+    /// doesn't intersect the polytope.
+    fn cross_section(&self, slice: &Hyperplane<f64>) -> Option<Self>;
   
 	  /// Checks if the polytope is [fissary](https://polytope.miraheze.org/wiki/Fissary).
     fn is_fissary(&self) -> bool;
@@ -1020,7 +1565,11 @@ impl ConcretePolytope for Concrete {
     ///
     /// # Panics
     /// This method shouldn't panic. If it does, please file a bug.
-    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError> {
+    fn try_dual_mut_with_tol(
+        &mut self,
+        sphere: &Hypersphere<f64>,
+        tol: f64,
+    ) -> Result<(), Self::DualError> {
         // If we're dealing with a nullitope, the dual is itself.
         let rank = self.rank();
         if rank == 0 {
@@ -1056,7 +1605,7 @@ impl ConcretePolytope for Concrete {
 
         // Reciprocates the projected points.
         for (idx, v) in projections.iter_mut().enumerate() {
-            if !sphere.reciprocate_mut(v) && rank != 1 {
+            if !sphere.reciprocate_mut_with_tol(v, tol) && rank != 1 {
                 return Err(DualError(idx));
             }
         }
@@ -1167,7 +1716,7 @@ impl ConcretePolytope for Concrete {
     ///
     /// # Todo
     /// We should make this function take a general [`Subspace`] instead.
-    fn cross_section(&self, slice: &Hyperplane<f64>) -> Self {
+    fn cross_section(&self, slice: &Hyperplane<f64>) -> Option<Self> {
         if self.rank() < 4 {
             unreachable!();
         }
@@ -1195,7 +1744,7 @@ impl ConcretePolytope for Concrete {
 
         // The slice does not intersect the polytope.
         if vertex_count == 0 {
-            return Self::nullitope();
+            return None;
         }
 
         ranks.push(SubelementList::min());
@@ -1291,7 +1840,7 @@ impl ConcretePolytope for Concrete {
         unsafe {
             let mut abs = builder.build();
             abs.untangle_faces();
-            Self::new(vertices, abs)
+            Some(Self::new(vertices, abs))
         }
     }
 
@@ -1390,8 +1939,14 @@ impl ConcretePolytope for Concrete {
 
 #[cfg(test)]
 mod tests {
-    use super::{Concrete, ConcretePolytope};
-    use crate::{float::Float, Polytope};
+    use super::{AntiprismError, Concrete, ConcreteError, ConcretePolytope};
+    use crate::{
+        abs::{Abstract, Ranked},
+        file::FromFile,
+        float::Float,
+        geometry::{Hyperplane, Hypersphere, Point},
+        Polytope,
+    };
 
     use approx::abs_diff_eq;
 
@@ -1549,6 +2104,430 @@ mod tests {
         }
     }
 
+    /// Checks the volume of a unit edge regular tetrahedron against its exact
+    /// value √2 / 12.
+    #[test]
+    fn tetrahedron() {
+        test_volume(Concrete::tetrahedron(), Some(f64::SQRT_2 / 12.0));
+    }
+
+    /// Checks that the volume doesn't depend on the position of the polytope.
+    #[test]
+    fn offset_cube() {
+        let mut cube = Concrete::cube();
+        cube.recenter_with(&vec![-3.0, 1.5, 7.0].into());
+        test_volume(cube, Some(1.0));
+    }
+
+    /// Checks that slicing a cube through its center, parallel to a face,
+    /// gives a unit square.
+    #[test]
+    fn cross_section_cube() {
+        let hyperplane = Hyperplane::new(vec![0.0, 0.0, 1.0].into(), 0.0);
+        let square = Concrete::cube().cross_section(&hyperplane).unwrap();
+
+        crate::test(&square, [1, 4, 4, 1]);
+        assert!(square.is_equilateral_with(1.0));
+    }
+
+    /// Checks that a plane that misses a cube gives no cross-section.
+    #[test]
+    fn cross_section_miss() {
+        let hyperplane = Hyperplane::new(vec![0.0, 0.0, 1.0].into(), 2.0);
+        assert!(Concrete::cube().cross_section(&hyperplane).is_none());
+    }
+
+    /// Checks that points have no inradius.
+    #[test]
+    fn point_radii() {
+        let point = Concrete::point();
+        assert_eq!(point.circumradius(), Some(0.0));
+        assert_eq!(point.inradius(), None);
+    }
+
+    #[test]
+    fn cube_radii() {
+        let mut cube = Concrete::cube();
+        cube.recenter_with(&vec![1.0, 2.0, 3.0].into());
+        cube.element_sort();
+
+        let circumradius = cube.circumradius().unwrap();
+        let inradius = cube.inradius().unwrap();
+        assert!(abs_diff_eq!(circumradius, f64::SQRT_3 / 2.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(inradius, 0.5, epsilon = f64::EPS));
+    }
+
+    /// Checks that cuboids are circumscribable, but that a cube with a
+    /// displaced vertex isn't.
+    #[test]
+    fn circumradius_irregular() {
+        assert!(Concrete::dyad_with(2.0).prism().prism().circumradius().is_some());
+
+        let mut cube = Concrete::cube();
+        cube.vertices[0] *= 2.0;
+        assert!(cube.circumradius().is_none());
+    }
+
+    /// An OFF file for a square toroid, made out of a square prism with a
+    /// square hole.
+    const TOROID: &str = "OFF
+        16 16 32
+        -1.5 -1.5 1
+        1.5 -1.5 1
+        1.5 1.5 1
+        -1.5 1.5 1
+        -0.5 -0.5 1
+        0.5 -0.5 1
+        0.5 0.5 1
+        -0.5 0.5 1
+        -1.5 -1.5 0
+        1.5 -1.5 0
+        1.5 1.5 0
+        -1.5 1.5 0
+        -0.5 -0.5 0
+        0.5 -0.5 0
+        0.5 0.5 0
+        -0.5 0.5 0
+        4 0 1 5 4
+        4 1 2 6 5
+        4 2 3 7 6
+        4 3 0 4 7
+        4 8 9 13 12
+        4 9 10 14 13
+        4 10 11 15 14
+        4 11 8 12 15
+        4 0 1 9 8
+        4 1 2 10 9
+        4 2 3 11 10
+        4 3 0 8 11
+        4 4 5 13 12
+        4 5 6 14 13
+        4 6 7 15 14
+        4 7 4 12 15";
+
+    #[test]
+    fn convex() {
+        assert!(Concrete::cube().is_convex());
+        assert!(Concrete::hypercube(5).is_convex());
+        assert!(Concrete::polygon(7).is_convex());
+        assert!(Concrete::dyad().is_convex());
+        assert!(Concrete::point().is_convex());
+    }
+
+    #[test]
+    fn non_convex() {
+        let toroid = Concrete::from_off(TOROID).unwrap();
+        crate::test(&toroid, [1, 16, 32, 16, 1]);
+
+        assert!(!toroid.is_convex());
+        assert!(!Concrete::star_polygon(5, 2).is_convex());
+    }
+
+    /// Checks the edge lengths of a unit cube.
+    #[test]
+    fn edge_lengths() {
+        let cube = Concrete::cube();
+        let lengths = cube.edge_lengths();
+        assert_eq!(lengths.len(), 12);
+        assert!(lengths
+            .iter()
+            .all(|&len| abs_diff_eq!(len, 1.0, epsilon = f64::EPS)));
+        assert!(cube.is_unit_edge(f64::EPS));
+
+        let mut cube = cube;
+        cube.scale(1.01);
+        assert!(!cube.is_unit_edge(f64::EPS));
+        assert!(cube.is_unit_edge(0.1));
+    }
+
+    /// Checks the dihedral angles of a cube, a pentagon, a dyad and a point.
+    #[test]
+    fn dihedral_angles() {
+        let cube = Concrete::cube();
+        let angles = cube.dihedral_angles();
+        assert_eq!(angles.len(), 12);
+        for angle in angles {
+            assert!(abs_diff_eq!(angle.unwrap(), f64::PI / 2.0, epsilon = f64::EPS));
+        }
+
+        let pentagon = Concrete::polygon(5);
+        for angle in pentagon.dihedral_angles() {
+            assert!(abs_diff_eq!(angle.unwrap(), f64::PI * 0.6, epsilon = f64::EPS));
+        }
+
+        // Every vertex of the skeleton of a cube is in three edges.
+        assert!(cube.skeleton(2).dihedral_angle(0).is_none());
+        assert!(cube.dihedral_angle(12).is_none());
+
+        // Dyads and points have no ridges to measure.
+        assert!(Concrete::dyad().dihedral_angles().is_empty());
+        assert!(Concrete::point().dihedral_angles().is_empty());
+    }
+
+    /// Checks that a flattened square isn't convex.
+    #[test]
+    fn non_convex_degenerate() {
+        let mut square = Concrete::polygon(4);
+        square.vertices.iter_mut().for_each(|v| v[1] = 0.0);
+        assert!(!square.is_convex());
+    }
+
+    /// Checks that a square embedded in 4D flattens back into 2D, and that a
+    /// cube is left untouched.
+    #[test]
+    fn flatten() {
+        let mut square = Concrete::polygon(4);
+        let len = square.edge_len(0).unwrap();
+        square
+            .vertices
+            .iter_mut()
+            .for_each(|v| *v = vec![v[0], 1.0, v[1], -1.0].into());
+
+        square.flatten();
+        assert_eq!(square.dim(), Some(2));
+        assert!(square.is_equilateral_with(len));
+        assert!(square.is_convex());
+
+        let mut cube = Concrete::cube();
+        let vertices = cube.vertices.clone();
+        cube.flatten();
+        assert_eq!(cube.vertices, vertices);
+    }
+
+    /// Checks that truncating a cube yields a convex truncated cube.
+    #[test]
+    fn truncate_cube() {
+        let truncated = Concrete::cube().truncate();
+        crate::test(&truncated, [1, 24, 36, 14, 1]);
+        assert!(truncated.is_convex());
+    }
+
+    /// Truncates a cube at a few depths.
+    #[test]
+    fn truncate_at() {
+        let cube = Concrete::cube();
+
+        let cuboctahedron = cube.truncate_at(0.0);
+        crate::test(&cuboctahedron, [1, 12, 24, 14, 1]);
+        assert!(cuboctahedron.is_equilateral_with(f64::HALF_SQRT_2));
+
+        // The depth at which the octagons become regular.
+        let depth = f64::SQRT_2 - 1.0;
+        let truncated = cube.truncate_at(depth);
+        crate::test(&truncated, [1, 24, 36, 14, 1]);
+        assert!(truncated.is_equilateral_with(depth));
+
+        assert_eq!(cube.truncate_at(1.0).vertices, cube.vertices);
+    }
+
+    /// Checks that the tolerance for a facet to pass through the
+    /// reciprocation center is respected.
+    #[test]
+    fn dual_tolerance() {
+        // A dyad with a vertex at distance 0.001 from the origin.
+        let mut dyad = Concrete::dyad();
+        dyad.recenter_with(&vec![-0.499].into());
+        let sphere = Hypersphere::unit(1);
+
+        assert!(dyad.clone().try_dual_mut_with_tol(&sphere, 0.01).is_err());
+
+        let mut dual = dyad.clone();
+        assert!(dual.try_dual_mut_with_tol(&sphere, 0.0001).is_ok());
+        assert!(dual.vertices.iter().any(|v| (v[0] + 1000.0).abs() < 1e-6));
+    }
+
+    /// Checks each of the ways building an antiprism can fail.
+    #[test]
+    fn antiprism_errors() {
+        let cube = Concrete::cube();
+
+        assert_eq!(
+            cube.try_antiprism_with(&Hypersphere::unit(2), 1.0).unwrap_err(),
+            AntiprismError::WrongDimension {
+                expected: 3,
+                found: 2
+            }
+        );
+
+        let point = Hypersphere::with_squared_radius(Point::zeros(3), 0.0);
+        assert_eq!(
+            cube.try_antiprism_with(&point, 1.0).unwrap_err(),
+            AntiprismError::DegenerateSphere
+        );
+
+        // The center lies on one of the facets.
+        let sphere = Hypersphere::with_squared_radius(vec![0.5, 0.0, 0.0].into(), 1.0);
+        assert!(matches!(
+            cube.try_antiprism_with(&sphere, 1.0),
+            Err(AntiprismError::FacetThroughCenter(_))
+        ));
+
+        let antiprism = cube.try_antiprism_with(&Hypersphere::unit(3), 1.0).unwrap();
+        crate::test(&antiprism, [1, 14, 48, 62, 28, 1]);
+    }
+
+    /// Triangulates a cube.
+    #[test]
+    fn triangulate_cube() {
+        let cube = Concrete::cube();
+        let (vertices, triangles) = cube.triangulate();
+        assert_eq!(vertices, cube.vertices);
+        assert_eq!(triangles.len(), 12);
+    }
+
+    /// Checks that star polygons are triangulated from their centroid.
+    #[test]
+    fn triangulate_star() {
+        let (vertices, triangles) = Concrete::star_polygon(5, 2).triangulate();
+        assert_eq!(vertices.len(), 6);
+        assert_eq!(triangles.len(), 5);
+    }
+
+    /// Checks that the Petrie polygon of a cube is a skew hexagon.
+    #[test]
+    fn petrie_polygon_cube() {
+        let hexagon = Concrete::cube().petrie_polygon().unwrap();
+        crate::test(&hexagon, [1, 6, 6, 1]);
+        assert!(hexagon.is_equilateral_with(1.0));
+    }
+
+    /// Checks that omnitruncating a cube yields a great rhombicuboctahedron.
+    #[test]
+    fn omnitruncate_cube() {
+        let omnitruncate = Concrete::cube().omnitruncate();
+        crate::test(&omnitruncate, [1, 48, 72, 26, 1]);
+        assert!(omnitruncate.is_convex());
+    }
+
+    /// Checks that rectifying a cube yields a cuboctahedron.
+    #[test]
+    fn rectify_cube() {
+        let cuboctahedron = Concrete::cube().rectify();
+        crate::test(&cuboctahedron, [1, 12, 24, 14, 1]);
+        assert!(cuboctahedron.is_equilateral_with(f64::HALF_SQRT_2));
+    }
+
+    /// Builds a unit square from its abstract polytope and its vertices.
+    #[test]
+    fn try_new() {
+        let vertices: Vec<Point<f64>> = vec![
+            vec![0.0, 0.0].into(),
+            vec![1.0, 0.0].into(),
+            vec![1.0, 1.0].into(),
+            vec![0.0, 1.0].into(),
+        ];
+
+        let square = Concrete::try_new(vertices.clone(), Abstract::polygon(4)).unwrap();
+        crate::test(&square, [1, 4, 4, 1]);
+        assert!(square.is_equilateral_with(1.0));
+
+        assert_eq!(
+            Concrete::try_new(vertices[..3].to_vec(), Abstract::polygon(4)).unwrap_err(),
+            ConcreteError::VertexCount {
+                expected: 4,
+                found: 3
+            }
+        );
+
+        let mut skew = vertices;
+        skew[2] = vec![1.0, 1.0, 1.0].into();
+        assert_eq!(
+            Concrete::try_new(skew, Abstract::polygon(4)).unwrap_err(),
+            ConcreteError::Dimension {
+                idx: 2,
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+
+    /// Checks that alternating a cube yields a regular tetrahedron.
+    #[test]
+    fn alternate_cube() {
+        let tetrahedron = Concrete::cube().alternate().unwrap();
+        crate::test(&tetrahedron, [1, 4, 6, 4, 1]);
+        assert!(tetrahedron.is_equilateral_with(f64::SQRT_2));
+    }
+
+    /// Checks that snubbing a cube yields a snub cube.
+    #[test]
+    fn snub_cube() {
+        let snub = Concrete::cube().snub().unwrap();
+        crate::test(&snub, [1, 24, 60, 38, 1]);
+        assert_eq!(snub.vertices.len(), 24);
+    }
+
+    /// Checks that a tesseract can be projected into 3D.
+    #[test]
+    fn project_tesseract() {
+        let tesseract = Concrete::hypercube(5).project_to_3d();
+
+        crate::test(&tesseract, [1, 16, 32, 24, 8, 1]);
+        assert_eq!(tesseract.vertices.len(), 16);
+        assert!(tesseract.vertices.iter().all(|v| v.len() == 3));
+    }
+
+    /// Checks that a polygon gets padded when projected into 3D.
+    #[test]
+    fn project_polygon() {
+        let polygon = Concrete::polygon(5);
+        let projected = polygon.project_to_3d();
+
+        for (v, w) in polygon.vertices.iter().zip(&projected.vertices) {
+            assert_eq!(w.len(), 3);
+            assert_eq!((v[0], v[1], 0.0), (w[0], w[1], w[2]));
+        }
+    }
+
+    #[test]
+    fn cuboid() {
+        test_volume(Concrete::dyad_with(2.0).prism().prism(), Some(2.0));
+    }
+
+    /// Checks that the centroid of a polytope is close to an expected point.
+    fn test_centroid(mut poly: Concrete, centroid: Point<f64>) {
+        poly.element_sort();
+        let poly_centroid = poly.centroid().expect("Polytope has no centroid!");
+
+        assert!(
+            abs_diff_eq!((&poly_centroid - &centroid).norm(), 0.0, epsilon = f64::EPS),
+            "Expected centroid {}, found centroid {}.",
+            centroid,
+            poly_centroid
+        );
+    }
+
+    #[test]
+    fn centroid_offset_cube() {
+        let mut cube = Concrete::cube();
+        cube.recenter_with(&vec![-3.0, 1.5, 7.0].into());
+        test_centroid(cube, vec![3.0, -1.5, -7.0].into());
+    }
+
+    #[test]
+    fn centroid_tetrahedron() {
+        let tet = Concrete::new(
+            vec![
+                vec![0.0, 0.0, 0.0].into(),
+                vec![1.0, 0.0, 0.0].into(),
+                vec![0.0, 2.0, 0.0].into(),
+                vec![0.0, 0.0, 3.0].into(),
+            ],
+            Abstract::tetrahedron(),
+        );
+
+        test_centroid(tet, vec![0.25, 0.5, 0.75].into());
+    }
+
+    /// Checks that degenerate polytopes have no centroid.
+    #[test]
+    fn centroid_degenerate() {
+        let mut square = Concrete::polygon(4);
+        square.vertices.iter_mut().for_each(|v| v[1] = 0.0);
+        square.element_sort();
+        assert!(square.centroid().is_none());
+    }
+
     #[test]
     fn orthoplex() {
         for n in 1..=6 {
@@ -1558,4 +2537,45 @@ mod tests {
             );
         }
     }
+
+    /// Checks that a concrete polytope is exactly equal to another, both in
+    /// its vertices and in its abstract structure.
+    fn assert_same(p: &Concrete, q: &Concrete) {
+        assert_eq!(p.vertices, q.vertices);
+        assert_eq!(p.abs.ranks(), q.abs.ranks());
+    }
+
+    /// Checks that the nullitope is the identity for the pyramid product, and
+    /// that the point is the identity for the prism and tegum products.
+    #[test]
+    fn identities() {
+        let nullitope = Concrete::nullitope();
+        let point = Concrete::point();
+
+        for p in [Concrete::dyad(), Concrete::polygon(5), Concrete::cube()] {
+            assert_same(&nullitope.duopyramid(&p), &p);
+            assert_same(&p.duopyramid(&nullitope), &p);
+            assert_same(&point.duoprism(&p), &p);
+            assert_same(&p.duoprism(&point), &p);
+            assert_same(&point.duotegum(&p), &p);
+            assert_same(&p.duotegum(&point), &p);
+        }
+    }
+
+    /// Checks that a cube can be rescaled to a different edge length.
+    #[test]
+    fn scale_to_edge_length() {
+        let mut cube = Concrete::cube();
+        assert!(cube.scale_to_edge_length(2.0).is_ok());
+        assert!(cube.is_equilateral_with(2.0));
+    }
+
+    /// Checks that polytopes that aren't equilateral, or that don't have edges,
+    /// aren't rescaled.
+    #[test]
+    fn scale_to_edge_length_fail() {
+        let mut cuboid = Concrete::dyad_with(2.0).prism().prism();
+        assert!(cuboid.scale_to_edge_length(1.0).is_err());
+        assert!(Concrete::point().scale_to_edge_length(1.0).is_err());
+    }
 }
@@ -8,13 +8,16 @@ use crate::{
     float::Float,
     group::Group,
     geometry::{Matrix, Point, PointOrd, Subspace},
-    Polytope,
+    BudgetExceeded, Polytope, DEFAULT_BUDGET,
 };
 
 use vec_like::*;
 
 use super::ConcretePolytope;
 
+/// A symmetry group of a polytope, along with a list of vertex mappings.
+pub type SymmetryGroup = (Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>);
+
 impl Flag {
     /// Outputs a sequence of vertices obtained from applying a fixed sequence of flag changes to a flag.
     /// Used for computing the elements of a symmetry group. 
@@ -40,6 +43,26 @@ impl Flag {
 impl Concrete {
     /// Computes the symmetry group of a polytope, along with a list of vertex mappings.
     pub fn get_symmetry_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>)> {
+        // We can't possibly go through more than `usize::MAX` flags.
+        self.symmetry_group_with_budget(usize::MAX).ok().flatten()
+    }
+
+    /// Computes the symmetry group of a polytope, along with a list of vertex
+    /// mappings. Returns an error if this requires going through more than a
+    /// given number of flags. If no budget is given, uses [`DEFAULT_BUDGET`].
+    pub fn try_symmetry_group(
+        &mut self,
+        budget: Option<usize>,
+    ) -> Result<Option<SymmetryGroup>, BudgetExceeded> {
+        self.symmetry_group_with_budget(budget.unwrap_or(DEFAULT_BUDGET))
+    }
+
+    /// Computes the symmetry group of a polytope, along with a list of vertex
+    /// mappings, going through at most `budget` flags.
+    fn symmetry_group_with_budget(
+        &mut self,
+        budget: usize,
+    ) -> Result<Option<SymmetryGroup>, BudgetExceeded> {
         let mut fixed = self.clone(); // We'll relabel the facets if needed so the first facet isn't hemi.
 
         let mut facet_idx = 0;
@@ -56,7 +79,7 @@ impl Concrete {
 
             if facet_idx == self.el_count(self.rank()-1) {
                 println!("Symmetry calculation failed. All facets pass through the origin.");
-                return None
+                return Ok(None)
             }
 
             if facet_idx != 0 {
@@ -99,11 +122,18 @@ impl Concrete {
 
         let base_flag = fixed.first_flag();
         let base_basis = base_flag.clone().vertex_sequence(&fixed);
-        let base_basis_inverse = base_basis.clone().try_inverse().unwrap();
+        let base_basis_inverse = match base_basis.clone().try_inverse() {
+            Some(inverse) => inverse,
+            None => return Ok(None),
+        };
 
         let mut group = Vec::<Matrix<f64>>::new();
 
-        'a: for flag in flag_iter {
+        'a: for (count, flag) in flag_iter.enumerate() {
+            if count == budget {
+                return Err(BudgetExceeded(budget));
+            }
+
             if flag
                 .iter()
                 .enumerate()
@@ -147,10 +177,47 @@ impl Concrete {
         }
 
         unsafe {
-            Some((Group::new(&self.rank()-1, group.into_iter()), vertex_map))
+            Ok(Some((Group::new(&self.rank()-1, group.into_iter()), vertex_map)))
         }
     }
 
+    /// Determines whether a polytope is geometrically
+    /// [regular](https://polytope.miraheze.org/wiki/Regular_polytope), i.e.
+    /// whether its symmetry group acts transitively on its flags. Since only
+    /// the identity fixes a flag of a full-rank polytope, this amounts to
+    /// checking that it has as many symmetries as flags.
+    ///
+    /// The polytope is recentered at its gravicenter and flattened before the
+    /// check. Polytopes that still aren't full-rank, like skew polygons, are
+    /// never considered regular.
+    pub fn is_regular(&self) -> bool {
+        // We can't possibly go through more than `usize::MAX` flags.
+        self.try_is_regular(Some(usize::MAX)).unwrap_or(false)
+    }
+
+    /// Determines whether a polytope is geometrically regular, as in
+    /// [`Self::is_regular`]. Returns an error if computing its symmetry group
+    /// or counting its flags requires going through more than a given number
+    /// of flags. If no budget is given, uses [`DEFAULT_BUDGET`].
+    pub fn try_is_regular(&self, budget: Option<usize>) -> Result<bool, BudgetExceeded> {
+        let rank = self.rank();
+        if rank < 2 {
+            return Ok(true);
+        }
+
+        let mut poly = self.clone();
+        poly.recenter();
+        poly.flatten();
+        if poly.dim() != Some(rank - 1) {
+            return Ok(false);
+        }
+
+        Ok(match poly.try_symmetry_group(budget)? {
+            Some((group, _)) => group.count() == poly.try_flag_count(budget)?,
+            None => false,
+        })
+    }
+
     /// Computes the rotation subgroup of a polytope, along with a list of vertex mappings.
     pub fn get_rotation_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>)> {
         if let Some((full_group, full_vertex_map)) = self.get_symmetry_group() {
@@ -249,4 +316,47 @@ impl Vertices {
             vertex_map,
         )
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conc::{Concrete, ConcretePolytope},
+        geometry::{Matrix, Vector},
+        Polytope,
+    };
+
+    /// Checks that computing a symmetry group stops as soon as it goes over
+    /// budget.
+    #[test]
+    fn budget() {
+        let mut cube = Concrete::cube();
+
+        assert!(cube.try_symmetry_group(Some(1)).is_err());
+        assert_eq!(cube.try_symmetry_group(None).unwrap().unwrap().0.count(), 48);
+    }
+
+    /// Checks that a cube and a pentagram are regular, but a cuboid isn't.
+    #[test]
+    fn regular() {
+        assert!(Concrete::cube().is_regular());
+        assert!(Concrete::star_polygon(5, 2).is_regular());
+
+        let cuboid =
+            Concrete::cube().apply(&Matrix::from_diagonal(&Vector::from_vec(vec![1.0, 2.0, 3.0])));
+        assert!(!cuboid.is_regular());
+
+        // Regularity doesn't depend on the position of the polytope.
+        let mut cube = Concrete::cube();
+        cube.recenter_with(&vec![1.0, 2.0, 3.0].into());
+        assert!(cube.is_regular());
+    }
+
+    /// Checks that regularity checks respect their budget.
+    #[test]
+    fn try_regular() {
+        let cube = Concrete::cube();
+        assert!(cube.try_is_regular(Some(1)).is_err());
+        assert!(cube.try_is_regular(None).unwrap());
+    }
+}
@@ -2,7 +2,10 @@
 
 use std::fmt::Display;
 
-use petgraph::graph::{Edge as GraphEdge, Node as GraphNode, NodeIndex, UnGraph};
+use petgraph::{
+    graph::{Edge as GraphEdge, Node as GraphNode, NodeIndex, UnGraph},
+    visit::Dfs,
+};
 
 use crate::{
     float::Float,
@@ -15,30 +18,42 @@ use super::{parse::CdBuilder, Cox};
 pub type CdResult<T> = Result<T, CdError>;
 
 /// Represents an error while parsing a CD.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum CdError {
     /// A parenthesis was opened but not closed.
     MismatchedParenthesis {
         /// The position at which the reader found the error.
         pos: usize,
+
+        /// The text starting at the unclosed parenthesis.
+        context: String,
     },
 
     /// The diagram ended unexpectedly.
     UnexpectedEnding {
         /// The position at which the reader found the error.
         pos: usize,
+
+        /// The last few characters before the ending.
+        context: String,
     },
 
     /// A number couldn't be parsed.
     ParseError {
         /// The position at which the reader found the error.
         pos: usize,
+
+        /// The text that couldn't be parsed.
+        context: String,
     },
 
     /// An invalid symbol was found.
     InvalidSymbol {
         /// The position at which the reader found the error.
         pos: usize,
+
+        /// The invalid symbol.
+        context: String,
     },
 
     /// An invalid edge was found.
@@ -61,28 +76,60 @@ pub enum CdError {
         /// The second node in the duplicated edge.
         b: usize,
     },
+
+    /// The diagram doesn't describe a spherical symmetry group.
+    NonSpherical,
+
+    /// The diagram doesn't describe a Euclidean symmetry group.
+    NonEuclidean,
+
+    /// Some connected component of the diagram has no ringed nodes.
+    NonMinimal,
+
+    /// The diagram isn't connected, where it must be.
+    Disconnected {
+        /// The index of the first node not connected to the first one.
+        idx: usize,
+    },
+
+    /// A snub node was found where it isn't supported.
+    SnubNode {
+        /// The index of the snub node.
+        idx: usize,
+    },
+
+    /// Two components of a compound diagram have different dimensions.
+    DimensionMismatch {
+        /// The dimension of the first component.
+        first: usize,
+
+        /// The dimension of the other component.
+        other: usize,
+    },
 }
 
 impl Display for CdError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
+        match self {
             // A parenthesis was opened but not closed.
-            Self::MismatchedParenthesis { pos } => {
-                write!(f, "mismatched parenthesis at position {}", pos)
+            Self::MismatchedParenthesis { pos, context } => {
+                write!(f, "mismatched parenthesis in '{}' at position {}", context, pos)
             }
 
             // The diagram ended unexpectedly.
-            Self::UnexpectedEnding { pos } => {
-                write!(f, "CD ended unexpectedly at position {}", pos)
+            Self::UnexpectedEnding { pos, context } => {
+                write!(f, "CD ended unexpectedly after '{}' at position {}", context, pos)
             }
 
             // A number couldn't be parsed.
-            Self::ParseError { pos } => {
-                write!(f, "parsing failed at position {}", pos)
+            Self::ParseError { pos, context } => {
+                write!(f, "could not parse '{}' at position {}", context, pos)
             }
 
             // An invalid symbol was found.
-            Self::InvalidSymbol { pos } => write!(f, "invalid symbol found at position {}", pos),
+            Self::InvalidSymbol { pos, context } => {
+                write!(f, "invalid symbol '{}' at position {}", context, pos)
+            }
 
             // An invalid edge was found.
             Self::InvalidEdge { num, den, pos } => {
@@ -93,12 +140,62 @@ impl Display for CdError {
             Self::RepeatEdge { a, b } => {
                 write!(f, "repeat edge between {} and {}", a, b)
             }
+
+            // The diagram doesn't describe a spherical symmetry group.
+            Self::NonSpherical => write!(f, "CD is not spherical"),
+
+            // The diagram doesn't describe a Euclidean symmetry group.
+            Self::NonEuclidean => write!(f, "CD is not Euclidean"),
+
+            // Some connected component of the diagram has no ringed nodes.
+            Self::NonMinimal => write!(f, "CD has a component with no ringed nodes"),
+
+            // The diagram isn't connected.
+            Self::Disconnected { idx } => {
+                write!(f, "CD is disconnected at node with index {}", idx)
+            }
+
+            // A snub node was found where it isn't supported.
+            Self::SnubNode { idx } => write!(f, "unsupported snub node at index {}", idx),
+
+            // Two components of a compound diagram have different dimensions.
+            Self::DimensionMismatch { first, other } => write!(
+                f,
+                "compound components have different dimensions {} and {}",
+                first, other
+            ),
         }
     }
 }
 
 impl std::error::Error for CdError {}
 
+/// The characters that represent ringed nodes in inline notation, along with
+/// the node values they stand for. Most of these are the lengths of the
+/// diagonals of regular polygons.
+pub const SHORTCHORDS: &[(char, f64)] = &[
+    ('v', (f64::SQRT_5 - f64::ONE) / f64::TWO),
+    ('x', f64::ONE),
+    ('q', f64::SQRT_2),
+    ('f', (f64::SQRT_5 + f64::ONE) / f64::TWO),
+    ('h', f64::SQRT_3),
+    // sqrt(2 + sqrt(2))
+    ('k', 1.847_759_065_022_573_5),
+    // sqrt((5 + sqrt(5)) / 2)
+    ('j', 1.902_113_032_590_307),
+    ('u', f64::TWO),
+    ('w', f64::SQRT_2 + f64::ONE),
+    ('F', (f64::SQRT_5 + f64::THREE) / f64::TWO),
+    ('e', f64::SQRT_3 + f64::ONE),
+    ('Q', f64::SQRT_2 * f64::TWO),
+    ('d', f64::THREE),
+    ('V', f64::SQRT_5 + f64::ONE),
+    ('U', f64::SQRT_2 + f64::TWO),
+    ('A', (f64::SQRT_5 + f64::ONE) / f64::FOUR + f64::ONE),
+    ('X', f64::SQRT_2 * f64::TWO + f64::ONE),
+    ('B', f64::SQRT_5 + f64::TWO),
+];
+
 /// A node in a [`Cd`]. Represents a mirror in hyperspace, and specifies both
 /// where a generator point should be located with respect to it, and how it
 /// should interact with it.
@@ -140,45 +237,65 @@ impl Node {
         Self::Snub(x)
     }
 
-    /// Returns whether this node is ringed.
+    /// Returns a pseudo-ringed node, i.e. a ringed node at zero distance from
+    /// the generator. Its [value](Self::value) is the same as that of an
+    /// unringed node, but it still counts as ringed.
+    pub fn pseudo() -> Self {
+        Self::Ringed(0.0)
+    }
+
+    /// Returns whether this node is ringed. This includes
+    /// [pseudo-ringed](Self::pseudo) nodes.
     pub fn is_ringed(&self) -> bool {
         matches!(self, Self::Ringed(_))
     }
 
+    /// Returns whether this node represents an active mirror, i.e. whether it's
+    /// either ringed or snub.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Ringed(_) | Self::Snub(_))
+    }
+
     /// Converts the character into a node value, using [Wendy Krieger's
     /// scheme](https://polytope.miraheze.org/wiki/Coxeter_diagram#Different_edge_lengths).
-    ///
-    /// # Todo
-    /// Make this customizable?
+    /// Besides `o` and `s`, the valid characters are those in [`SHORTCHORDS`].
     pub fn from_char(c: char) -> Option<Self> {
-        Some(Node::ringed(match c {
-            'o' => return Some(Node::Unringed),
-            's' => return Some(Node::snub(f64::ONE)),
-            'v' => (f64::SQRT_5 - f64::ONE) / f64::TWO,
-            'x' => f64::ONE,
-            'q' => f64::SQRT_2,
-            'f' => (f64::SQRT_5 + f64::ONE) / f64::TWO,
-            'h' => f64::SQRT_3,
-            'k' => (f64::SQRT_2 + f64::TWO).fsqrt(),
-            'u' => f64::TWO,
-            'w' => f64::SQRT_2 + f64::ONE,
-            'F' => (f64::SQRT_5 + f64::THREE) / f64::TWO,
-            'e' => f64::SQRT_3 + f64::ONE,
-            'Q' => f64::SQRT_2 * f64::TWO,
-            'd' => f64::THREE,
-            'V' => f64::SQRT_5 + f64::ONE,
-            'U' => f64::SQRT_2 + f64::TWO,
-            'A' => (f64::SQRT_5 + f64::ONE) / f64::FOUR + f64::ONE,
-            'X' => f64::SQRT_2 * f64::TWO + f64::ONE,
-            'B' => f64::SQRT_5 + f64::TWO,
-            _ => return None,
-        }))
+        match c {
+            'o' => Some(Node::Unringed),
+            's' => Some(Node::snub(f64::ONE)),
+            _ => SHORTCHORDS
+                .iter()
+                .find(|&&(d, _)| c == d)
+                .map(|&(_, x)| Node::ringed(x)),
+        }
+    }
+
+    /// Returns the character that represents this node, if any. This is the
+    /// inverse of [`Self::from_char`].
+    pub fn to_char(&self) -> Option<char> {
+        match *self {
+            Node::Unringed => Some('o'),
+            Node::Snub(x) => {
+                if (x - f64::ONE).abs() < f64::EPS {
+                    Some('s')
+                } else {
+                    None
+                }
+            }
+            Node::Ringed(x) => SHORTCHORDS
+                .iter()
+                .find(|&&(_, y)| (x - y).abs() < f64::EPS)
+                .map(|&(c, _)| c),
+        }
     }
 
     /// Attempts to convert a character into a [`Node`]. Returns a
     /// [`CdError::InvalidSymbol`] if it fails.
     pub fn from_char_or(c: char, pos: usize) -> CdResult<Self> {
-        Self::from_char(c).ok_or(CdError::InvalidSymbol { pos })
+        Self::from_char(c).ok_or_else(|| CdError::InvalidSymbol {
+            pos,
+            context: c.to_string(),
+        })
     }
 }
 
@@ -193,6 +310,35 @@ impl Display for Node {
     }
 }
 
+/// The direction of an [`Edge`] in a [`Cd`]. Arrows are used in Dynkin
+/// diagrams to mark that the roots on either side have different lengths.
+/// They don't change the angle between the mirrors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeDir {
+    /// The edge has no arrow.
+    #[default]
+    Undirected,
+
+    /// The arrow points from the first node in the edge to the other one.
+    /// Written as `>`.
+    Forward,
+
+    /// The arrow points from the other node in the edge to the first one.
+    /// Written as `<`.
+    Backward,
+}
+
+impl Display for EdgeDir {
+    /// Prints the arrow corresponding to the direction.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Undirected => Ok(()),
+            Self::Forward => write!(f, ">"),
+            Self::Backward => write!(f, "<"),
+        }
+    }
+}
+
 /// Represents the value of an edge in a [`Cd`]. An edge with a value of `x`
 /// represents an angle of π / *x* between two hyperplanes.
 #[derive(Clone, Copy, Debug)]
@@ -202,6 +348,9 @@ pub struct Edge {
 
     /// The denominator of the edge.
     pub den: u32,
+
+    /// The direction of the edge.
+    pub dir: EdgeDir,
 }
 
 impl Edge {
@@ -209,7 +358,11 @@ impl Edge {
     /// are invalid, returns a [`CdError::InvalidEdge`].
     pub fn rational(num: u32, den: u32, pos: usize) -> CdResult<Self> {
         if num > 1 && den != 0 && den < num {
-            Ok(Self { num, den })
+            Ok(Self {
+                num,
+                den,
+                dir: EdgeDir::Undirected,
+            })
         } else {
             Err(CdError::InvalidEdge { num, den, pos })
         }
@@ -221,6 +374,11 @@ impl Edge {
         Self::rational(num, 1, pos)
     }
 
+    /// Returns the same edge with a given direction.
+    pub fn with_dir(self, dir: EdgeDir) -> Self {
+        Self { dir, ..self }
+    }
+
     /// Returns the numerical value of the edge.
     pub fn value(&self) -> f64 {
         f64::u32(self.num) / f64::u32(self.den)
@@ -236,9 +394,9 @@ impl Display for Edge {
     /// Prints the value contained in an edge.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.den == 1 {
-            write!(f, "{}", self.num)
+            write!(f, "{}{}", self.num, self.dir)
         } else {
-            write!(f, "{} / {}", self.num, self.den)
+            write!(f, "{} / {}{}", self.num, self.den, self.dir)
         }
     }
 }
@@ -277,6 +435,16 @@ impl NodeRef {
             Self::Negative(idx) => len - 1 - idx,
         })
     }
+
+    /// Returns the index in the graph that the node reference represents, or
+    /// `None` if there's no such node. Requires knowing the number of nodes in
+    /// the graph.
+    pub fn try_index(&self, len: usize) -> Option<NodeIndex> {
+        match *self {
+            Self::Absolute(idx) | Self::Negative(idx) if idx >= len => None,
+            _ => Some(self.index(len)),
+        }
+    }
 }
 
 /// Stores the [`NodeRef`]s of both ends of an edge, along with its value.
@@ -320,7 +488,7 @@ impl EdgeRef {
 /// perpendicular.
 ///
 /// To actually build a Coxeter diagram, we use a [`CdBuilder`].
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Cd(UnGraph<Node, Edge>);
 
 impl Cd {
@@ -335,6 +503,59 @@ impl Cd {
         CdBuilder::new(input).build()
     }
 
+    /// Parses a [Schläfli symbol](https://polytope.miraheze.org/wiki/Schläfli_symbol)
+    /// such as `{4,3}` or `{5/2,5}` into the linear Coxeter diagram of the
+    /// regular polytope it describes, whose first node is ringed. The symbol
+    /// `{}` stands for the dyad.
+    pub fn from_schlafli(input: &str) -> CdResult<Self> {
+        let start = input.len() - input.trim_start().len();
+        let symbol = input.trim();
+
+        if !symbol.starts_with('{') {
+            return Err(CdError::InvalidSymbol {
+                pos: start,
+                context: symbol.chars().next().map(String::from).unwrap_or_default(),
+            });
+        }
+
+        if symbol.len() < 2 || !symbol.ends_with('}') {
+            return Err(CdError::MismatchedParenthesis {
+                pos: start,
+                context: symbol.to_string(),
+            });
+        }
+
+        let mut cd = Self::new();
+        let mut prev = cd.add_node(Node::ringed(1.0));
+        let inner = &symbol[1..symbol.len() - 1];
+        if inner.trim().is_empty() {
+            return Ok(cd);
+        }
+
+        // Reads every entry, keeping track of its position in the input.
+        let mut pos = start + 1;
+        for entry in inner.split(',') {
+            let parse = |s: &str| {
+                s.trim().parse().map_err(|_| CdError::ParseError {
+                    pos,
+                    context: entry.trim().to_string(),
+                })
+            };
+
+            let edge = match entry.split_once('/') {
+                Some((num, den)) => Edge::rational(parse(num)?, parse(den)?, pos)?,
+                None => Edge::int(parse(entry)?, pos)?,
+            };
+
+            let node = cd.add_node(Node::Unringed);
+            cd.add_edge(prev, node, edge)?;
+            prev = node;
+            pos += entry.len() + 1;
+        }
+
+        Ok(cd)
+    }
+
     /// The dimension of the polytope the Coxeter diagram describes.
     pub fn dim(&self) -> usize {
         self.node_count()
@@ -365,6 +586,11 @@ impl Cd {
         self.0.add_node(node)
     }
 
+    /// Returns a mutable reference to the node with a given index.
+    pub fn node_mut(&mut self, idx: NodeIndex) -> &mut Node {
+        &mut self.0[idx]
+    }
+
     /// Adds an edge into the Coxeter diagram.
     pub fn add_edge(&mut self, a: NodeIndex, b: NodeIndex, edge: Edge) -> CdResult<()> {
         if !edge.eq_two() {
@@ -398,6 +624,28 @@ impl Cd {
         Vector::from_iterator(self.dim(), self.node_iter().map(|node| node.value()))
     }
 
+    /// Returns the index of the first node that isn't connected to the first
+    /// node of the diagram, if any.
+    pub fn second_component(&self) -> Option<usize> {
+        if self.dim() == 0 {
+            return None;
+        }
+
+        let mut reached = vec![false; self.dim()];
+        let mut dfs = Dfs::new(&self.0, NodeIndex::new(0));
+        while let Some(node) = dfs.next(&self.0) {
+            reached[node.index()] = true;
+        }
+
+        reached.iter().position(|&r| !r)
+    }
+
+    /// Returns whether the diagram is connected. Disconnected diagrams
+    /// describe products of the polytopes of each component.
+    pub fn is_connected(&self) -> bool {
+        self.second_component().is_none()
+    }
+
     /// Returns whether a CD is minimal, i.e. whether every connected component
     /// has at least one ringed node.
     pub fn minimal(&self) -> bool {
@@ -438,6 +686,136 @@ impl Cd {
         Cox::new(matrix)
     }
 
+    /// Writes the Coxeter diagram back in inline ASCII notation, like `x4o3o`.
+    /// Returns `None` if the diagram isn't linear, i.e. if some edge doesn't
+    /// join two consecutive nodes.
+    pub fn to_linear_string(&self) -> Option<String> {
+        if !self.is_linear() {
+            return None;
+        }
+
+        let mut string = String::new();
+        for (i, node) in self.node_iter().enumerate() {
+            // Writes the edge between this node and the previous one.
+            if i != 0 {
+                match self.0.find_edge(NodeIndex::new(i - 1), NodeIndex::new(i)) {
+                    Some(idx) => {
+                        let edge = self.0[idx];
+                        if edge.den == 1 {
+                            string.push_str(&edge.num.to_string());
+                        } else {
+                            string.push_str(&format!("{}/{}", edge.num, edge.den));
+                        }
+                        string.push_str(&edge.dir.to_string());
+                    }
+                    None => string.push(' '),
+                }
+            }
+
+            match (node.to_char(), node) {
+                (Some(c), _) => string.push(c),
+                (None, Node::Ringed(x)) => string.push_str(&format!("({})", x)),
+                (None, Node::Snub(x)) => string.push_str(&format!("s({})", x)),
+                (None, Node::Unringed) => unreachable!("unringed nodes are written as o"),
+            }
+        }
+
+        Some(string)
+    }
+
+    /// Creates a Coxeter matrix from a Coxeter diagram, in which every entry is
+    /// stored as an exact fraction `(num, den)`. In contrast to [`Self::cox`],
+    /// this keeps track of the exact numerator and denominator of every edge.
+    pub fn cox_frac(&self) -> Matrix<(i32, i32)> {
+        let dim = self.dim();
+        let graph = &self.0;
+
+        Matrix::from_fn(dim, dim, |i, j| {
+            // Every entry in the diagonal of a Coxeter matrix is 1.
+            if i == j {
+                return (1, 1);
+            }
+
+            // If an edge connects two nodes, it adds its value to the matrix.
+            if let Some(idx) = graph.find_edge(NodeIndex::new(i), NodeIndex::new(j)) {
+                let edge = graph[idx];
+                (edge.num as i32, edge.den as i32)
+            }
+            // Else, we write a 2.
+            else {
+                (2, 1)
+            }
+        })
+    }
+
+    /// Returns whether the diagram describes an affine Coxeter group, i.e. the
+    /// symmetry group of a Euclidean tiling. See [`Cox::is_euclidean`].
+    pub fn is_affine(&self) -> bool {
+        self.cox().is_euclidean()
+    }
+
+    /// Returns whether every edge in the diagram joins two consecutive nodes.
+    fn is_linear(&self) -> bool {
+        self.raw_edges().iter().all(|edge| {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            a + 1 == b || b + 1 == a
+        })
+    }
+
+    /// Draws the Coxeter diagram as a TikZ picture, to be used in LaTeX.
+    /// Ringed nodes are drawn filled, unringed nodes are drawn empty, and
+    /// snub nodes are drawn half-filled.
+    ///
+    /// Linear diagrams are laid out from left to right. Any other diagram has
+    /// its nodes laid out in a circle, in order.
+    pub fn to_tikz(&self) -> String {
+        let dim = self.dim();
+        let linear = self.is_linear();
+        let mut tikz = String::from("\\begin{tikzpicture}\n");
+
+        for (i, node) in self.node_iter().enumerate() {
+            let (x, y) = if linear {
+                (i as f64, 0.0)
+            } else {
+                let (sin, cos) = (f64::TAU * i as f64 / dim as f64).fsin_cos();
+                (cos, sin)
+            };
+
+            let style = match node {
+                Node::Unringed => "",
+                Node::Ringed(_) => ", fill=black",
+                Node::Snub(_) => {
+                    ", path picture={\\fill[black] (path picture bounding box.south west) \
+                    rectangle (path picture bounding box.north);}"
+                }
+            };
+
+            tikz.push_str(&format!(
+                "    \\node[draw, circle{}] (n{}) at ({:.3}, {:.3}) {{}};\n",
+                style, i, x, y
+            ));
+        }
+
+        for edge in self.raw_edges() {
+            let weight = edge.weight;
+            let label = if weight.den == 1 {
+                weight.num.to_string()
+            } else {
+                format!("{}/{}", weight.num, weight.den)
+            };
+
+            tikz.push_str(&format!(
+                "    \\draw (n{}) -- node[above] {{${}$}} (n{});\n",
+                edge.source().index(),
+                label,
+                edge.target().index()
+            ));
+        }
+
+        tikz.push_str("\\end{tikzpicture}\n");
+        tikz
+    }
+
     /// Returns the circumradius of the polytope specified by the matrix, or
     /// `None` if this doesn't apply. This is just
     /// calling [`Self::generator`] and taking the norm.
@@ -446,17 +824,96 @@ impl Cd {
     }
 
     /// Returns a point in the position specified by the Coxeter diagram,
-    /// using the set of mirrors generated by [`Cox::normals`].    
+    /// using the set of mirrors generated by [`Cox::normals`]. The distance
+    /// from the point to each mirror is half the value of its node.
     pub fn generator(&self) -> Option<Point<f64>> {
-        let mut vector = self.node_vector();
+        let mut vector = self.node_vector() / 2.0;
 
+        // The normals are the columns of an upper triangular matrix, so we
+        // solve against its transpose.
         self.cox()
             .normals()?
-            .solve_upper_triangular_mut(&mut vector)
+            .tr_solve_upper_triangular_mut(&mut vector)
             .then(|| vector)
     }
 }
 
+/// The way in which the layers of a [`Lace`] are joined together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaceType {
+    /// Every layer is laced to every other layer. A lace simplex with two
+    /// layers is a lace prism. Written as `&#x`.
+    Simplex,
+
+    /// Every layer is laced only to the layers next to it. Written as `&#xt`.
+    Tower,
+}
+
+/// Encodes a [lace prism](https://polytope.miraheze.org/wiki/Lace_prism), lace
+/// simplex, or [lace tower](https://polytope.miraheze.org/wiki/Lace_tower).
+/// These are polytopes made out of a series of parallel layers with the same
+/// symmetry, joined by lacing edges.
+///
+/// Each layer is described by its own [`Cd`]. All of these share the same
+/// edges, and differ only in their nodes. For more information on the
+/// notation, see [`CdBuilder::build_lace`].
+#[derive(Clone)]
+pub struct Lace {
+    /// The Coxeter diagrams of each of the layers, in order.
+    pub layers: Vec<Cd>,
+
+    /// The length of the lacing edges.
+    pub lace_len: f64,
+
+    /// The way in which the layers are joined together.
+    pub lace_type: LaceType,
+}
+
+impl Lace {
+    /// Parses a lace diagram from ASCII inline notation. For more information,
+    /// see [`CdBuilder::build_lace`].
+    pub fn parse(input: &str) -> CdResult<Self> {
+        CdBuilder::build_lace(input)
+    }
+
+    /// Returns the number of layers in the lace diagram.
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+}
+
+/// Encodes a compound of Coxeter diagrams, each of which describes one of the
+/// components of a compound polytope. These are written as a list of diagrams
+/// separated by `+`, like `x3o3o + o3o3x`.
+#[derive(Clone)]
+pub struct Compound {
+    /// The Coxeter diagrams of each of the components, in order.
+    pub components: Vec<Cd>,
+}
+
+impl Compound {
+    /// Parses a compound diagram from ASCII inline notation. For more
+    /// information, see [`CdBuilder::build_compound`].
+    pub fn parse(input: &str) -> CdResult<Self> {
+        CdBuilder::build_compound(input)
+    }
+
+    /// Returns the number of components in the compound diagram.
+    pub fn component_count(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns the dimension of each of the components.
+    pub fn dims(&self) -> Vec<usize> {
+        self.components.iter().map(Cd::dim).collect()
+    }
+
+    /// Returns the [`Cox`] of each of the components.
+    pub fn coxes(&self) -> Vec<Cox<f64>> {
+        self.components.iter().map(Cd::cox).collect()
+    }
+}
+
 impl From<Cd> for Cox<f64> {
     fn from(cd: Cd) -> Self {
         cd.cox()
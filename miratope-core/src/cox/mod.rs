@@ -2,15 +2,21 @@
 
 pub mod cd;
 pub mod parse;
+pub mod wythoff;
 
 use std::{
+    collections::BTreeSet,
     iter,
     ops::{Index, IndexMut},
 };
 
 use crate::float::Float;
 use crate::group::Group;
-use crate::{geometry::Matrix, group::GenIter};
+use crate::{
+    geometry::{Matrix, Point, PointOrd},
+    group::GenIter,
+    BudgetExceeded, DEFAULT_BUDGET,
+};
 
 use nalgebra::dmatrix;
 
@@ -93,9 +99,9 @@ impl Cox<f64> {
     }
 
     /// Creates a Coxeter matrix from a linear diagram, whose edges are
-    /// described by the vector.
+    /// described by the vector. A diagram with n edges has n + 1 nodes.
     pub fn from_lin_diagram(diagram: &[f64]) -> Self {
-        Self::from_lin_diagram_iter(diagram.iter().copied(), diagram.len())
+        Self::from_lin_diagram_iter(diagram.iter().copied(), diagram.len() + 1)
     }
 
     /// Returns the Coxeter matrix for the I2(x) group.
@@ -140,8 +146,78 @@ impl Cox<f64> {
         )
     }
 
+    /// Returns the Schläfli matrix of the Coxeter group, whose (i, j) entry is
+    /// the dot product of the unit normals of the ith and jth mirrors, namely
+    /// −cos(π / m) for the corresponding entry m of the Coxeter matrix.
+    pub fn schlafli(&self) -> Matrix<f64> {
+        self.0.map(|m| -(f64::PI / m).fcos())
+    }
+
+    /// Recovers a Coxeter matrix from its [Schläfli matrix](Self::schlafli),
+    /// mapping every entry s to π / arccos(−s). Diagonal entries and right
+    /// angles are mapped exactly to 1 and 2, respectively.
+    pub fn from_schlafli(schlafli: &Matrix<f64>) -> Self {
+        Self(Matrix::from_fn(schlafli.nrows(), schlafli.ncols(), |i, j| {
+            let s = schlafli[(i, j)];
+
+            if i == j {
+                1.0
+            } else if s.abs() < f64::EPS {
+                2.0
+            } else {
+                f64::PI / (-s).clamp(-1.0, 1.0).acos()
+            }
+        }))
+    }
+
+    /// Returns the eigenvalues of the Schläfli matrix.
+    fn schlafli_eigenvalues(&self) -> Vec<f64> {
+        self.schlafli().symmetric_eigenvalues().iter().copied().collect()
+    }
+
+    /// Returns whether the Coxeter group is finite, i.e. whether the Schläfli
+    /// matrix is positive definite.
+    pub fn is_spherical(&self) -> bool {
+        self.schlafli_eigenvalues().iter().all(|&x| x > f64::EPS)
+    }
+
+    /// Returns whether the Coxeter group is the symmetry group of a Euclidean
+    /// tiling, i.e. whether the Schläfli matrix is positive semidefinite but
+    /// singular.
+    pub fn is_euclidean(&self) -> bool {
+        let eigenvalues = self.schlafli_eigenvalues();
+
+        eigenvalues.iter().all(|&x| x > -f64::EPS)
+            && eigenvalues.iter().any(|x| x.abs() < f64::EPS)
+    }
+
+    /// Returns whether the Coxeter group acts on hyperbolic space, i.e.
+    /// whether the Schläfli matrix has a negative eigenvalue.
+    pub fn is_hyperbolic(&self) -> bool {
+        self.schlafli_eigenvalues().iter().any(|&x| x < -f64::EPS)
+    }
+
+    /// Fills in the entries above the diagonal of the `i`-th column of an
+    /// upper triangular matrix, so that its dot products with the previous
+    /// columns match the values in the Coxeter matrix. Returns the squared
+    /// norm of the column so far.
+    fn fill_normal(&self, mat: &mut Matrix<f64>, i: usize) -> f64 {
+        let (prev_gens, mut n_i) = mat.columns_range_pair_mut(0..i, i);
+
+        for (j, n_j) in prev_gens.column_iter().enumerate() {
+            // All other entries in the dot product between columns are zero.
+            let dot = n_i.rows_range(0..=j).dot(&n_j.rows_range(0..=j));
+            n_i[j] = (-(f64::PI / self[(i, j)]).fcos() - dot) / n_j[j];
+        }
+
+        n_i.norm_squared()
+    }
+
     /// Returns an upper triangular matrix whose columns are unit normal vectors
-    /// for the hyperplanes described by the Coxeter matrix.
+    /// for the hyperplanes described by the Coxeter matrix. The dot products
+    /// of these normals are the entries of the [Schläfli matrix](Self::schlafli),
+    /// so that the normals of two mirrors at an angle of π / m make an angle of
+    /// π − π / m.
     pub fn normals(&self) -> Option<Matrix<f64>> {
         let dim = self.dim();
         let mut mat = Matrix::zeros(dim, dim);
@@ -149,20 +225,49 @@ impl Cox<f64> {
         // Builds each column from the top down, so that each of the succesive
         // dot products we check match the values in the Coxeter matrix.
         for i in 0..dim {
-            let (prev_gens, mut n_i) = mat.columns_range_pair_mut(0..i, i);
-
-            for (j, n_j) in prev_gens.column_iter().enumerate() {
-                // All other entries in the dot product between columns are zero.
-                let dot = n_i.rows_range(0..=j).dot(&n_j.rows_range(0..=j));
-                n_i[j] = ((f64::PI / self[(i, j)]).fcos() - dot) / n_j[j];
-            }
-
             // If the vector doesn't fit in spherical space.
-            let norm_sq: f64 = n_i.norm_squared();
+            let norm_sq = self.fill_normal(&mut mat, i);
             if norm_sq >= 1.0 - f64::EPS {
                 return None;
             } else {
-                n_i[i] = (1.0 - norm_sq).fsqrt();
+                mat[(i, i)] = (1.0 - norm_sq).fsqrt();
+            }
+        }
+
+        Some(mat)
+    }
+
+    /// Returns the mirrors of a Euclidean Coxeter group, whose last mirror is
+    /// determined by the others, as an upper triangular matrix. This is the
+    /// case for any connected affine diagram.
+    ///
+    /// The mirrors live in a space of one dimension less than the matrix. The
+    /// last entry of every column stores the offset of the mirror from the
+    /// origin, so that a column `(n, c)` represents the hyperplane
+    /// `n · x + c = 0`. Only the last mirror doesn't pass through the origin.
+    ///
+    /// Returns `None` if the diagram isn't of this form. In particular, this
+    /// happens for any spherical diagram.
+    pub fn normals_euclidean(&self) -> Option<Matrix<f64>> {
+        let dim = self.dim();
+        let mut mat = Matrix::zeros(dim, dim);
+
+        for i in 0..dim {
+            let norm_sq = self.fill_normal(&mut mat, i);
+
+            // The last vector must already be a unit vector.
+            if i + 1 == dim {
+                if (norm_sq - 1.0).abs() >= f64::EPS {
+                    return None;
+                }
+
+                mat[(i, i)] = 1.0;
+            }
+            // Every other vector must fit in spherical space.
+            else if norm_sq >= 1.0 - f64::EPS {
+                return None;
+            } else {
+                mat[(i, i)] = (1.0 - norm_sq).fsqrt();
             }
         }
 
@@ -197,4 +302,127 @@ impl Cox<f64> {
     pub fn group(&self) -> Option<Group<GenIter<Matrix<f64>>>> {
         self.gen_iter().map(Into::into)
     }
+
+    /// Returns the order of the Coxeter group, or `None` if it isn't finite.
+    /// Gives up after [`DEFAULT_BUDGET`] elements.
+    pub fn group_order(&self) -> Option<u64> {
+        self.try_group_order(None).ok().flatten()
+    }
+
+    /// Returns the order of the Coxeter group, or `Ok(None)` if its mirrors
+    /// don't fit in spherical space. Returns an error if the group has more
+    /// than a given number of elements. If no budget is given, uses
+    /// [`DEFAULT_BUDGET`].
+    pub fn try_group_order(&self, budget: Option<usize>) -> Result<Option<u64>, BudgetExceeded> {
+        let budget = budget.unwrap_or(DEFAULT_BUDGET);
+        let normals = match self.normals() {
+            Some(normals) => normals,
+            None => return Ok(None),
+        };
+
+        // A point in the interior of the fundamental chamber. The elements of
+        // the group are in bijection with its orbit.
+        let mut point = Point::from_element(self.dim(), 1.0);
+        if !normals.tr_solve_upper_triangular_mut(&mut point) {
+            return Ok(None);
+        }
+
+        let mut orbit = vec![point.clone()];
+        let mut found = BTreeSet::new();
+        found.insert(PointOrd::new(point));
+
+        let mut idx = 0;
+        while idx < orbit.len() {
+            for n in normals.column_iter() {
+                let image = &orbit[idx] - n * (2.0 * n.dot(&orbit[idx]));
+
+                if found.insert(PointOrd::new(image.clone())) {
+                    if orbit.len() == budget {
+                        return Err(BudgetExceeded(budget));
+                    }
+
+                    orbit.push(image);
+                }
+            }
+
+            idx += 1;
+        }
+
+        Ok(Some(orbit.len() as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_order() {
+        assert_eq!(Cox::a(3).group_order(), Some(24));
+        assert_eq!(Cox::b(3).group_order(), Some(48));
+        assert_eq!(Cox::h(3).group_order(), Some(120));
+        assert_eq!(Cox::d(4).group_order(), Some(192));
+        assert_eq!(Cox::i2(5.0).group_order(), Some(10));
+    }
+
+    #[test]
+    fn classification() {
+        let a3 = Cox::a(3);
+        assert!(a3.is_spherical());
+        assert!(!a3.is_euclidean());
+        assert!(!a3.is_hyperbolic());
+
+        let apeirogon = Cox::i2(f64::INFINITY);
+        assert!(!apeirogon.is_spherical());
+        assert!(apeirogon.is_euclidean());
+        assert!(!apeirogon.is_hyperbolic());
+
+        let order_5_square = Cox::parse("x4o5o").unwrap();
+        assert!(order_5_square.is_hyperbolic());
+    }
+
+    #[test]
+    fn schlafli() {
+        let schlafli = Cox::b(2).schlafli();
+        assert!((schlafli[(0, 1)] + f64::HALF_SQRT_2).abs() < f64::EPS);
+        assert!((schlafli[(0, 0)] - 1.0).abs() < f64::EPS);
+    }
+
+    #[test]
+    fn from_schlafli() {
+        let a3 = Cox::a(3);
+        let cox = Cox::from_schlafli(&a3.schlafli());
+        assert_eq!(cox[(0, 0)], 1.0);
+        assert_eq!(cox[(0, 2)], 2.0);
+        assert!(cox.0.iter().zip(a3.0.iter()).all(|(x, y)| (x - y).abs() < f64::EPS));
+
+        let apeirogon = Cox::from_schlafli(&Cox::i2(f64::INFINITY).schlafli());
+        assert_eq!(apeirogon[(0, 1)], f64::INFINITY);
+    }
+
+    #[test]
+    fn normals_euclidean() {
+        let apeirogon = Cox::i2(f64::INFINITY);
+        assert!(apeirogon.normals().is_none());
+
+        let normals = apeirogon.normals_euclidean().unwrap();
+        assert!((normals[(0, 1)] + 1.0).abs() < f64::EPS);
+        assert!((normals[(1, 1)] - 1.0).abs() < f64::EPS);
+
+        assert!(Cox::parse("x4o4o").unwrap().normals_euclidean().is_some());
+        assert!(Cox::parse("x3o3o3*a").unwrap().normals_euclidean().is_some());
+        assert!(Cox::b(3).normals_euclidean().is_none());
+        assert!(Cox::parse("x4o5o").unwrap().normals_euclidean().is_none());
+    }
+
+    #[test]
+    fn group_order_infinite() {
+        assert_eq!(Cox::parse("x4o4o").unwrap().group_order(), None);
+    }
+
+    #[test]
+    fn group_order_budget() {
+        assert!(Cox::b(4).try_group_order(Some(100)).is_err());
+        assert_eq!(Cox::b(4).try_group_order(Some(384)).unwrap(), Some(384));
+    }
 }
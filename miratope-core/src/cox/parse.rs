@@ -4,7 +4,43 @@ use std::{collections::VecDeque, iter, str::FromStr};
 
 use petgraph::graph::NodeIndex;
 
-use super::cd::{Cd, CdError, CdResult, Edge, EdgeRef, Node, NodeRef};
+use super::cd::{
+    Cd, CdError, CdResult, Compound, Edge, EdgeDir, EdgeRef, Lace, LaceType, Node, NodeRef,
+};
+
+/// The maximum number of characters shown as context for an error at the end
+/// of a diagram.
+const CONTEXT_LEN: usize = 3;
+
+/// Returns the last few characters of a diagram, to be used as the context of
+/// a [`CdError::UnexpectedEnding`].
+fn tail(diagram: &str) -> String {
+    let count = diagram.chars().count();
+    diagram.chars().skip(count.saturating_sub(CONTEXT_LEN)).collect()
+}
+
+/// Returns a [`CdError::InvalidSymbol`] for a given character.
+fn invalid_symbol(pos: usize, c: char) -> CdError {
+    CdError::InvalidSymbol {
+        pos,
+        context: c.to_string(),
+    }
+}
+
+/// Shifts the position of an error by a given offset. Used when the error was
+/// found while parsing a substring of a diagram.
+fn offset_error(mut err: CdError, offset: usize) -> CdError {
+    match &mut err {
+        CdError::MismatchedParenthesis { pos, .. }
+        | CdError::UnexpectedEnding { pos, .. }
+        | CdError::ParseError { pos, .. }
+        | CdError::InvalidSymbol { pos, .. }
+        | CdError::InvalidEdge { pos, .. } => *pos += offset,
+        _ => {}
+    }
+
+    err
+}
 
 /// Helper struct that parses a [`Cd`] based on a textual notation, adapted from
 /// [Krieger (year)](https://bendwavy.org/klitzing/pdf/Stott_v8.pdf).
@@ -29,12 +65,41 @@ use super::cd::{Cd, CdError, CdResult, Edge, EdgeRef, Node, NodeRef};
 ///
 /// * One character nodes, like `x` or `F`.
 /// * Parenthesized lengths, líke `(1.0)` or `(-3.5)`.
+/// * Snub nodes with parenthesized lengths, like `s(1.5)`.
 /// * Virtual nodes, like `*a` or `*-c`.
+/// * Virtual nodes with numeric indices, like `*{27}` or `*-{2}`. These allow
+///   referring to nodes beyond the 26th.
+///
+/// Virtual nodes are zero-indexed, so that `*a` and `*{0}` refer to the same
+/// node.
 ///
 /// Edges come in two different types:
 ///
 /// * A single integer, like `3` or `15`.
 /// * Two integers separated by a backslash, like `5/2` or `7/3`.
+///
+/// Any edge may be followed by an arrow, either `>` or `<`, which marks the
+/// direction of the edge. For instance, `x4>o3o`.
+///
+/// # Lace diagrams
+///
+/// A [`Lace`] diagram is written just like a Coxeter diagram, except that
+/// every one character node is replaced by a group of characters, one for each
+/// layer, and a suffix is appended at the end:
+///
+/// ```txt
+/// [diagram]  &#  [node]  t?
+/// ```
+///
+/// The node in the suffix specifies the length of the lacing edges. If the
+/// suffix ends in `t`, the diagram is a lace tower, otherwise it's a lace
+/// simplex. For instance, `xo4ox&#x` is a lace prism, and `xoo3oox&#xt` is a
+/// lace tower with three layers.
+///
+/// # Compound diagrams
+///
+/// A [`Compound`] diagram is a list of Coxeter diagrams separated by `+`, like
+/// `x3o3o + o3o3x`. Every one of them describes a component of the compound.
 pub struct CdBuilder<'a> {
     /// The Coxeter diagram in inline ASCII notation.
     diagram: &'a str,
@@ -57,6 +122,18 @@ pub struct CdBuilder<'a> {
 
     /// The value of the next edge.
     next_edge: Option<Edge>,
+
+    /// Every virtual node found, along with its position and text. We check
+    /// that these refer to actual nodes once we've read the entire diagram.
+    virtual_nodes: Vec<(NodeRef, usize, String)>,
+
+    /// The number of layers in the diagram. This is always 1, unless we're
+    /// parsing a lace diagram.
+    layers: usize,
+
+    /// If we're parsing a lace diagram, stores the values of each node in
+    /// each layer.
+    lace_nodes: Vec<Vec<Node>>,
 }
 
 /// Operations that are commonly done to parse CDs.
@@ -75,6 +152,20 @@ impl<'a> CdBuilder<'a> {
             // The previous and next node to be built.
             prev_node: None,
             next_edge: None,
+            virtual_nodes: Vec::new(),
+
+            // The layers of a lace diagram.
+            layers: 1,
+            lace_nodes: Vec::new(),
+        }
+    }
+
+    /// Initializes a new CD builder from a string, for a lace diagram with a
+    /// given number of layers.
+    fn with_layers(diagram: &'a str, layers: usize) -> Self {
+        Self {
+            layers,
+            ..Self::new(diagram)
         }
     }
 
@@ -86,7 +177,10 @@ impl<'a> CdBuilder<'a> {
     /// Returns a [`CdError::UnexpectedEnding`]. Such an error always occurs at
     /// the end of the diagram.
     fn unexpected_ending(&self) -> CdError {
-        CdError::UnexpectedEnding { pos: self.len() }
+        CdError::UnexpectedEnding {
+            pos: self.len(),
+            context: tail(self.diagram),
+        }
     }
 
     /// Gets the next index-character pair, or returns `None` if we've run out
@@ -129,6 +223,22 @@ impl<'a> CdBuilder<'a> {
         self.cd.add_node(node)
     }
 
+    /// Reads the nodes of a lace diagram corresponding to all layers but the
+    /// first, and adds the node for the first layer to the diagram.
+    fn add_lace_node(&mut self, first: Node) -> CdResult<()> {
+        let mut nodes = Vec::with_capacity(self.layers);
+        nodes.push(first);
+
+        for _ in 1..self.layers {
+            let (idx, c) = self.next_or()?;
+            nodes.push(Node::from_char_or(c, idx)?);
+        }
+
+        self.add_node(first);
+        self.lace_nodes.push(nodes);
+        Ok(())
+    }
+
     /// Enqueues an edge, so that it's added when the diagram is built.
     fn enqueue_edge(&mut self, edge: EdgeRef) {
         self.edge_queue.push_back(edge);
@@ -137,9 +247,12 @@ impl<'a> CdBuilder<'a> {
     /// Attempts to parse a subslice of characters, determined by the range
     /// `init_idx..=end_idx`. Returns a [`CdError::ParseError`] if it fails.
     fn parse_slice<U: FromStr>(&mut self, init_idx: usize, end_idx: usize) -> CdResult<U> {
-        self.diagram[init_idx..=end_idx]
-            .parse()
-            .map_err(|_| CdError::ParseError { pos: end_idx })
+        let slice = &self.diagram[init_idx..=end_idx];
+
+        slice.parse().map_err(|_| CdError::ParseError {
+            pos: end_idx,
+            context: slice.to_owned(),
+        })
     }
 
     /// Parses a multi-character node. This contains a floating point literal
@@ -158,7 +271,10 @@ impl<'a> CdBuilder<'a> {
 
                 // In case the user tries to literally write "NaN" (real funny).
                 return if val.is_nan() {
-                    Err(CdError::InvalidSymbol { pos: end_idx })
+                    Err(CdError::InvalidSymbol {
+                        pos: end_idx,
+                        context: self.diagram[init_idx..=end_idx].to_owned(),
+                    })
                 } else {
                     Ok(Node::ringed(val))
                 };
@@ -168,7 +284,10 @@ impl<'a> CdBuilder<'a> {
         }
 
         // We never found the matching parenthesis.
-        Err(CdError::MismatchedParenthesis { pos: self.len() })
+        Err(CdError::MismatchedParenthesis {
+            pos: self.len(),
+            context: self.diagram[init_idx - 1..].to_owned(),
+        })
     }
 
     /// Reads the next node in the diagram and adds it to the graph. Returns
@@ -184,14 +303,26 @@ impl<'a> CdBuilder<'a> {
         let mut new_node = NodeRef::Absolute(self.cd.node_count());
 
         match c {
+            // Lace diagrams can only have one character nodes.
+            '(' if self.layers != 1 => return Err(invalid_symbol(idx, c)),
+
             // If the node is various characters inside parentheses.
             '(' => {
                 let node = self.parse_node()?;
                 self.add_node(node);
             }
 
+            // If the node is a snub node with a length inside parentheses.
+            's' if self.layers == 1 && matches!(self.peek(), Some((_, '('))) => {
+                self.next();
+                let node = Node::snub(self.parse_node()?.value());
+                self.add_node(node);
+            }
+
             // If the node is a virtual node.
             '*' => {
+                let start = idx;
+
                 // Reads the index the virtual node refers to.
                 let (mut idx, mut c) = self.next_or()?;
 
@@ -204,18 +335,37 @@ impl<'a> CdBuilder<'a> {
                     c = new_c;
                 }
 
-                match c {
+                new_node = match c {
                     // A virtual node, from *a to *z.
-                    'a'..='z' => new_node = NodeRef::new(neg, c as usize - 'a' as usize),
+                    'a'..='z' => NodeRef::new(neg, c as usize - 'a' as usize),
+
+                    // A virtual node with a numeric index, like *{27}.
+                    '{' => loop {
+                        let (end_idx, c) = self.next_or()?;
+
+                        if c == '}' {
+                            break NodeRef::new(neg, self.parse_slice(idx + 1, end_idx - 1)?);
+                        }
+                    },
 
                     // Any other character is invalid.
-                    _ => return Err(CdError::InvalidSymbol { pos: idx }),
-                }
+                    _ => return Err(invalid_symbol(idx, c)),
+                };
+
+                let (end, _) = self.peek().unwrap_or((self.len(), ' '));
+                let context = self.diagram[start..end].to_owned();
+                self.virtual_nodes.push((new_node, start, context));
             }
 
             // If the node is a single character.
             _ => {
-                self.add_node(Node::from_char_or(c, idx)?);
+                let node = Node::from_char_or(c, idx)?;
+
+                if self.layers == 1 {
+                    self.add_node(node);
+                } else {
+                    self.add_lace_node(node)?;
+                }
             }
         }
 
@@ -269,23 +419,33 @@ impl<'a> CdBuilder<'a> {
                     init_idx = idx + 1;
                 }
 
-                // If we reached the next node.
-                '(' | '*' | ' ' | 'A'..='z' => {
+                // If we reached an arrow or the next node.
+                '>' | '<' | '(' | '*' | ' ' | 'A'..='z' => {
                     // Parse the last value (either the denominator in case of a
                     // fraction, or the single number otherwise).
                     let last = self.parse_slice(init_idx, end_idx)?;
 
-                    return Ok(Some(match numerator {
+                    let edge = match numerator {
                         Some(num) => Edge::rational(num, last, end_idx)?,
                         None => Edge::int(last, end_idx)?,
-                    }));
+                    };
+
+                    // Reads the arrow, if any.
+                    let dir = match c {
+                        '>' => EdgeDir::Forward,
+                        '<' => EdgeDir::Backward,
+                        _ => return Ok(Some(edge)),
+                    };
+
+                    self.next();
+                    return Ok(Some(edge.with_dir(dir)));
                 }
 
                 // Business as usual.
                 '0'..='9' => {}
 
                 // We found an unexpected symbol.
-                _ => return Err(CdError::InvalidSymbol { pos: idx }),
+                _ => return Err(invalid_symbol(idx, c)),
             }
 
             end_idx = idx;
@@ -303,6 +463,8 @@ impl<'a> CdBuilder<'a> {
         Ok(())
     }
 
+    /// Reads through the entire diagram, adding all nodes and enqueuing all
+    /// edges.
     fn read(&mut self) -> CdResult<()> {
         loop {
             self.create_node()?;
@@ -316,25 +478,158 @@ impl<'a> CdBuilder<'a> {
         }
     }
 
-    /// Finishes building the CD and returns it.
-    pub fn build(mut self) -> CdResult<Cd> {
-        // Reads through the diagram.
-        self.read()?;
+    /// Adds all of the enqueued edges to the diagram.
+    ///
+    /// # Errors
+    /// Returns a [`CdError::InvalidSymbol`] if a virtual node refers to a node
+    /// that doesn't exist.
+    fn add_edges(&mut self) -> CdResult<()> {
         let len = self.cd.node_count();
 
-        for edge_ref in self.edge_queue.into_iter() {
+        for (node_ref, pos, context) in &self.virtual_nodes {
+            if node_ref.try_index(len).is_none() {
+                return Err(CdError::InvalidSymbol {
+                    pos: *pos,
+                    context: context.clone(),
+                });
+            }
+        }
+
+        while let Some(edge_ref) = self.edge_queue.pop_front() {
             let [a, b] = edge_ref.indices(len);
             self.cd.add_edge(a, b, edge_ref.edge)?;
         }
 
+        Ok(())
+    }
+
+    /// Finishes building the CD and returns it.
+    pub fn build(mut self) -> CdResult<Cd> {
+        // Reads through the diagram.
+        self.read()?;
+        self.add_edges()?;
+
         Ok(self.cd)
     }
+
+    /// Parses a [`Lace`] diagram. For more information on the notation, see
+    /// the [`CdBuilder`] docs.
+    ///
+    /// # Errors
+    /// Returns a [`CdError::UnexpectedEnding`] if the lace suffix is missing or
+    /// incomplete, and a [`CdError::InvalidSymbol`] if it contains anything
+    /// other than a node and an optional `t`, or if the nodes in the diagram
+    /// don't have a character for each layer.
+    pub fn build_lace(diagram: &'a str) -> CdResult<Lace> {
+        let len = diagram.len();
+        let suffix_idx = diagram
+            .find("&#")
+            .ok_or_else(|| CdError::UnexpectedEnding {
+                pos: len,
+                context: tail(diagram),
+            })?;
+
+        // Reads the suffix.
+        let mut suffix = diagram[suffix_idx + 2..]
+            .char_indices()
+            .map(|(idx, c)| (suffix_idx + 2 + idx, c));
+
+        let lace_len = match suffix.next() {
+            Some((idx, c)) => Node::from_char_or(c, idx)?.value(),
+            None => {
+                return Err(CdError::UnexpectedEnding {
+                    pos: len,
+                    context: tail(diagram),
+                })
+            }
+        };
+
+        let lace_type = match suffix.next() {
+            None => LaceType::Simplex,
+            Some((_, 't')) => LaceType::Tower,
+            Some((idx, c)) => return Err(invalid_symbol(idx, c)),
+        };
+
+        if let Some((idx, c)) = suffix.next() {
+            return Err(invalid_symbol(idx, c));
+        }
+
+        // The number of layers is the number of characters in the first node.
+        let diagram = &diagram[..suffix_idx];
+        let layers = diagram
+            .trim_start()
+            .chars()
+            .take_while(char::is_ascii_alphabetic)
+            .count();
+
+        if layers < 2 {
+            return Err(CdError::InvalidSymbol {
+                pos: suffix_idx,
+                context: "&#".to_owned(),
+            });
+        }
+
+        // Builds the diagram, then sets the nodes of each layer.
+        let mut builder = Self::with_layers(diagram, layers);
+        builder.read()?;
+        builder.add_edges()?;
+
+        let layers = (0..layers)
+            .map(|i| {
+                let mut layer = builder.cd.clone();
+
+                for (n, nodes) in builder.lace_nodes.iter().enumerate() {
+                    *layer.node_mut(NodeIndex::new(n)) = nodes[i];
+                }
+
+                layer
+            })
+            .collect();
+
+        Ok(Lace {
+            layers,
+            lace_len,
+            lace_type,
+        })
+    }
+
+    /// Parses a [`Compound`] diagram. For more information on the notation,
+    /// see the [`CdBuilder`] docs.
+    ///
+    /// # Errors
+    /// Returns any error found while parsing any of the components, with its
+    /// position relative to the entire diagram.
+    pub fn build_compound(diagram: &'a str) -> CdResult<Compound> {
+        let mut components = Vec::new();
+        let mut start = 0;
+        let mut depth = 0usize;
+
+        // Splits the diagram at every top-level `+`.
+        let ending = iter::once((diagram.len(), '+'));
+        for (idx, c) in diagram.char_indices().chain(ending) {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                '+' if depth == 0 => {
+                    let cd = Self::new(diagram[start..idx].trim_end())
+                        .build()
+                        .map_err(|err| offset_error(err, start))?;
+
+                    components.push(cd);
+                    start = idx + 1;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Compound { components })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cox::Cox;
+    use crate::{cox::Cox, float::Float};
     use crate::geometry::Matrix;
     use nalgebra::dmatrix;
 
@@ -425,6 +720,61 @@ mod tests {
         )
     }
 
+    #[test]
+    /// Tests the predicates on each kind of node.
+    fn node_predicates() {
+        let unringed = Node::Unringed;
+        assert!(!unringed.is_ringed());
+        assert!(!unringed.is_active());
+
+        let pseudo = Node::pseudo();
+        assert_eq!(pseudo.value(), unringed.value());
+        assert!(pseudo.is_ringed());
+        assert!(pseudo.is_active());
+        assert_ne!(pseudo, unringed);
+
+        let ringed = x();
+        assert!(ringed.is_ringed());
+        assert!(ringed.is_active());
+
+        let snub = Node::snub(1.0);
+        assert!(!snub.is_ringed());
+        assert!(snub.is_active());
+    }
+
+    #[test]
+    /// Tests that Schläfli symbols give the same diagrams as inline notation.
+    fn schlafli() {
+        let cube = Cd::from_schlafli("{4,3}").unwrap();
+        assert_eq!(cube.nodes(), vec![x(), o(), o()]);
+        assert_eq!(cube.cox(), Cd::parse("x4o3o").unwrap().cox());
+
+        let gissid = Cd::from_schlafli(" {5/2, 5} ").unwrap();
+        assert_eq!(gissid.cox(), Cd::parse("x5/2o5o").unwrap().cox());
+        assert_eq!(Cd::from_schlafli("{}").unwrap().nodes(), vec![x()]);
+    }
+
+    #[test]
+    /// Tests that malformed Schläfli symbols are rejected.
+    fn schlafli_errors() {
+        assert!(matches!(
+            Cd::from_schlafli("{4,3"),
+            Err(CdError::MismatchedParenthesis { .. })
+        ));
+        assert!(matches!(
+            Cd::from_schlafli("4,3}"),
+            Err(CdError::InvalidSymbol { pos: 0, .. })
+        ));
+        assert!(matches!(
+            Cd::from_schlafli("{4,a}"),
+            Err(CdError::ParseError { pos: 3, .. })
+        ));
+        assert!(matches!(
+            Cd::from_schlafli("{4,1}"),
+            Err(CdError::InvalidEdge { num: 1, den: 1, .. })
+        ));
+    }
+
     #[test]
     /// Tests snub nodes.
     fn snubs() {
@@ -460,6 +810,41 @@ mod tests {
         )
     }
 
+    #[test]
+    /// Tests that fractional edges keep their numerator and denominator.
+    fn fractional_edge() {
+        let cd = Cd::parse("x5/2o").unwrap();
+        assert_eq!(cd.raw_edges()[0].weight.value(), 2.5);
+        assert_eq!(cd.cox()[(0, 1)], 2.5);
+        assert_eq!(cd.cox_frac()[(0, 1)], (5, 2));
+        assert_eq!(cd.cox_frac()[(1, 1)], (1, 1));
+    }
+
+    #[test]
+    /// Tests snub nodes with explicit lengths.
+    fn snub_lengths() {
+        test(
+            "s(1.5)4o",
+            vec![Node::snub(1.5), o()],
+            dmatrix![
+                1.0, 4.0;
+                4.0, 1.0
+            ],
+        );
+
+        round_trip("s(1.5)4o");
+    }
+
+    #[test]
+    /// Tests the shortchord for the diagonal of a decagon.
+    fn shortchord_j() {
+        let cd = Cd::parse("j5o").unwrap();
+        let j = ((5.0 + 5f64.sqrt()) / 2.0).sqrt();
+
+        assert!((cd.nodes()[0].value() - j).abs() < f64::EPS);
+        assert_eq!(cd.nodes()[0].to_char(), Some('j'));
+    }
+
     #[test]
     /// Tests some virtual node shenanigans.
     fn virtual_nodes() {
@@ -475,6 +860,27 @@ mod tests {
         )
     }
 
+    #[test]
+    /// Tests virtual nodes that refer to nodes beyond the 26th.
+    fn numeric_virtual_nodes() {
+        let mut diagram = "x".to_owned();
+        for _ in 1..30 {
+            diagram.push_str("3o");
+        }
+        diagram.push_str(" *{27}4o *-{0}5*{2}");
+
+        let cox = Cd::parse(&diagram).unwrap().cox();
+        assert_eq!(cox.dim(), 31);
+        assert_eq!(cox[(27, 30)], 4.0);
+        assert_eq!(cox[(30, 2)], 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidSymbol { pos: 4, context: \"*{5}\" }")]
+    fn missing_virtual_node() {
+        Cd::parse("x3o *{5}3o").unwrap();
+    }
+
     #[test]
     /// Tests that CDs with spaces parse properly.
     fn spaces() {
@@ -504,25 +910,146 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "MismatchedParenthesis { pos: 6 }")]
+    /// Tests a lace prism.
+    fn lace_prism() {
+        let lace = Lace::parse("xo4ox&#x").unwrap();
+        let cox = Cox::new(dmatrix![
+            1.0, 4.0;
+            4.0, 1.0
+        ]);
+
+        assert_eq!(lace.lace_type, LaceType::Simplex);
+        assert_eq!(lace.lace_len, 1.0);
+        assert_eq!(lace.layer_count(), 2);
+        assert_eq!(lace.layers[0].nodes(), vec![x(), o()]);
+        assert_eq!(lace.layers[1].nodes(), vec![o(), x()]);
+
+        for layer in &lace.layers {
+            assert_eq!(layer.cox(), cox);
+        }
+    }
+
+    #[test]
+    /// Tests a lace tower.
+    fn lace_tower() {
+        let lace = Lace::parse("xoo3oox&#qt").unwrap();
+
+        assert_eq!(lace.lace_type, LaceType::Tower);
+        assert_eq!(lace.lace_len, std::f64::consts::SQRT_2);
+        assert_eq!(lace.layer_count(), 3);
+        assert_eq!(lace.layers[0].nodes(), vec![x(), o()]);
+        assert_eq!(lace.layers[1].nodes(), vec![o(), o()]);
+        assert_eq!(lace.layers[2].nodes(), vec![o(), x()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "UnexpectedEnding { pos: 7, context: \"x&#\" }")]
+    fn lace_unexpected_ending() {
+        Lace::parse("xo4ox&#").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "UnexpectedEnding { pos: 4, context: \"o4o\" }")]
+    fn lace_missing_layer() {
+        Lace::parse("xo4o&#x").unwrap();
+    }
+
+    /// Tests that a diagram is written back as itself.
+    fn round_trip(diagram: &str) {
+        assert_eq!(
+            Cd::parse(diagram).unwrap().to_linear_string().as_deref(),
+            Some(diagram)
+        );
+    }
+
+    #[test]
+    fn linear_string() {
+        round_trip("x4o3o");
+        round_trip("s3s4o");
+        round_trip("x5/2o3(2.5)");
+        round_trip("x3o x");
+    }
+
+    #[test]
+    fn directed_edge() {
+        let cd = Cd::parse("x4>o3o").unwrap();
+        assert_eq!(cd.raw_edges()[0].weight.dir, EdgeDir::Forward);
+        assert_eq!(cd.raw_edges()[1].weight.dir, EdgeDir::Undirected);
+        round_trip("x4>o3o");
+        round_trip("o3o4<x");
+    }
+
+    #[test]
+    fn affine() {
+        assert!(Cd::parse("x3o3o3*a").unwrap().is_affine());
+        assert!(Cd::parse("x4o3o4o").unwrap().is_affine());
+        assert!(Cd::parse("x4>o3o4o").unwrap().is_affine());
+        assert!(!Cd::parse("x4o3o").unwrap().is_affine());
+        assert!(!Cd::parse("x5o3o4o").unwrap().is_affine());
+    }
+
+    #[test]
+    fn tikz() {
+        let tikz = Cd::parse("x3o3o").unwrap().to_tikz();
+        assert_eq!(tikz.matches("\\node").count(), 3);
+        assert_eq!(tikz.matches("\\draw").count(), 2);
+        assert_eq!(tikz.matches("fill=black").count(), 1);
+
+        let tikz = Cd::parse("x3o3o3*a").unwrap().to_tikz();
+        assert_eq!(tikz.matches("\\draw").count(), 3);
+    }
+
+    #[test]
+    fn linear_string_branched() {
+        assert_eq!(Cd::parse("o3o3o3o *b3o").unwrap().to_linear_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "MismatchedParenthesis { pos: 6, context: \"(1.0x\" }")]
     fn mismatched_parenthesis() {
         Cd::parse("x(1.0x").unwrap();
     }
 
     #[test]
-    #[should_panic(expected = "UnexpectedEnding { pos: 6 }")]
+    #[should_panic(expected = "UnexpectedEnding { pos: 6, context: \"3x3\" }")]
     fn unexpected_ending() {
         Cd::parse("x4x3x3").unwrap();
     }
 
     #[test]
-    #[should_panic(expected = "InvalidSymbol { pos: 2 }")]
+    #[should_panic(expected = "InvalidSymbol { pos: 2, context: \"⊕\" }")]
     fn invalid_symbol() {
         Cd::parse("x3⊕5o").unwrap();
     }
 
     #[test]
-    #[should_panic(expected = "ParseError { pos: 5 }")]
+    fn compound() {
+        let compound = Compound::parse("x3o3o + o3x").unwrap();
+        assert_eq!(compound.component_count(), 2);
+        assert_eq!(compound.dims(), vec![3, 2]);
+        assert_eq!(compound.coxes()[1], Cox::a(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidSymbol { pos: 8, context: \"z\" }")]
+    fn compound_invalid_symbol() {
+        Compound::parse("x3o + x3z").unwrap();
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            Cd::parse("x3z3o").unwrap_err().to_string(),
+            "invalid symbol 'z' at position 2"
+        );
+        assert_eq!(
+            Cd::parse("x4x3x3").unwrap_err().to_string(),
+            "CD ended unexpectedly after '3x3' at position 6"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ParseError { pos: 5, context: \"1.1.1\" }")]
     fn parse_error() {
         Cd::parse("(1.1.1)3(2.0)").unwrap();
     }
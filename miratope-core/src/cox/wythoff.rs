@@ -0,0 +1,501 @@
+//! Builds [Wythoffian polytopes](https://polytope.miraheze.org/wiki/Wythoffian_polytope)
+//! out of Coxeter diagrams.
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::cd::{Cd, CdError, CdResult, Compound, Node};
+use crate::{
+    abs::{AbstractBuilder, SubelementList},
+    conc::Concrete,
+    float::Float,
+    geometry::{Point, PointOrd, Vector},
+    Polytope,
+};
+
+use vec_like::*;
+
+/// Finds the orbit of a point under the reflections through a set of mirrors,
+/// each given by a unit normal `n` and an offset `c` representing the
+/// hyperplane `n · x + c = 0`. Also returns how every mirror permutes the
+/// points of the orbit. If a radius is given, only the points within it of the
+/// origin are kept, and the reflections of the others are left as `None`.
+fn orbit(
+    generator: Point<f64>,
+    mirrors: &[(Vector<f64>, f64)],
+    radius: Option<f64>,
+) -> (Vec<Point<f64>>, Vec<Vec<Option<usize>>>) {
+    let mut vertices = vec![generator.clone()];
+    let mut vertex_idx = BTreeMap::new();
+    vertex_idx.insert(PointOrd::new(generator), 0);
+    let mut perms = vec![Vec::new(); mirrors.len()];
+
+    let mut idx = 0;
+    while idx < vertices.len() {
+        for (perm, (n, c)) in perms.iter_mut().zip(mirrors) {
+            let image = &vertices[idx] - n * (2.0 * (n.dot(&vertices[idx]) + c));
+            if radius.is_some_and(|radius| image.norm() > radius + f64::EPS) {
+                perm.push(None);
+                continue;
+            }
+
+            let len = vertices.len();
+            let image_idx = *vertex_idx
+                .entry(PointOrd::new(image.clone()))
+                .or_insert(len);
+
+            if image_idx == len {
+                vertices.push(image);
+            }
+            perm.push(Some(image_idx));
+        }
+
+        idx += 1;
+    }
+
+    (vertices, perms)
+}
+
+/// Returns whether a sorted slice is contained in another sorted slice.
+fn is_subset(a: &[usize], b: &[usize]) -> bool {
+    let mut b = b.iter();
+    a.iter().all(|x| b.any(|y| y == x))
+}
+
+impl Cd {
+    /// Returns whether the nodes in a bitmask form the type of a face of the
+    /// Wythoffian polytope. This happens exactly when every connected
+    /// component of the induced subdiagram contains a ringed node.
+    fn is_face_type(&self, mask: usize) -> bool {
+        let nodes = self.nodes();
+        let mut visited = 0usize;
+
+        for start in 0..nodes.len() {
+            if mask & (1 << start) == 0 || visited & (1 << start) != 0 {
+                continue;
+            }
+
+            // Traverses the connected component of the starting node.
+            let mut ringed = false;
+            let mut stack = vec![start];
+            visited |= 1 << start;
+
+            while let Some(i) = stack.pop() {
+                ringed |= nodes[i].is_ringed();
+
+                for edge in self.raw_edges() {
+                    let (a, b) = (edge.source().index(), edge.target().index());
+                    let j = if a == i {
+                        b
+                    } else if b == i {
+                        a
+                    } else {
+                        continue;
+                    };
+
+                    if mask & (1 << j) != 0 && visited & (1 << j) == 0 {
+                        visited |= 1 << j;
+                        stack.push(j);
+                    }
+                }
+            }
+
+            if !ringed {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Checks that the diagram has no snub nodes and that every connected
+    /// component has a ringed node.
+    fn check_wythoffian(&self) -> CdResult<()> {
+        for (idx, node) in self.node_iter().enumerate() {
+            if let Node::Snub(_) = node {
+                return Err(CdError::SnubNode { idx });
+            }
+        }
+
+        if self.minimal() {
+            Ok(())
+        } else {
+            Err(CdError::NonMinimal)
+        }
+    }
+
+    /// Checks that the Wythoffian polytope described by the diagram can be
+    /// built by [`Self::build`]. This is much cheaper than building it.
+    pub fn check(&self) -> CdResult<()> {
+        if self.dim() == 0 {
+            return Ok(());
+        }
+
+        self.check_wythoffian()?;
+        self.generator().map(|_| ()).ok_or(CdError::NonSpherical)
+    }
+
+    /// Builds the Wythoffian polytope described by the Coxeter diagram, by
+    /// reflecting the [generator](Self::generator) through the mirrors given by
+    /// [`Cox::normals`](super::Cox::normals).
+    ///
+    /// Only spherical diagrams without snub nodes are currently supported.
+    pub fn build(&self) -> CdResult<Concrete> {
+        let dim = self.dim();
+        if dim == 0 {
+            return Ok(Concrete::point());
+        }
+
+        self.check_wythoffian()?;
+        let normals = self.cox().normals().ok_or(CdError::NonSpherical)?;
+        let generator = self.generator().ok_or(CdError::NonSpherical)?;
+
+        let mirrors: Vec<_> = normals.column_iter().map(|n| (n.into_owned(), 0.0)).collect();
+        let (vertices, perms) = orbit(generator, &mirrors, None);
+        Ok(self.build_with(vertices, &perms))
+    }
+
+    /// Builds the patch of the Wythoffian tiling described by a Euclidean
+    /// Coxeter diagram, made out of all elements whose vertices are within a
+    /// given radius of the origin. The mirrors are given by
+    /// [`Cox::normals_euclidean`](super::Cox::normals_euclidean), so the
+    /// diagram must be connected, and one of the generator's vertices lies at
+    /// the origin. Disconnected diagrams return a [`CdError::Disconnected`].
+    ///
+    /// The result isn't a valid polytope, as the elements on the boundary of
+    /// the patch are missing some of their neighbors.
+    pub fn build_tiling(&self, radius: f64) -> CdResult<Concrete> {
+        let dim = self.dim();
+        if dim == 0 {
+            return Err(CdError::NonEuclidean);
+        }
+
+        self.check_wythoffian()?;
+        if let Some(idx) = self.second_component() {
+            return Err(CdError::Disconnected { idx });
+        }
+
+        let normals = self.cox().normals_euclidean().ok_or(CdError::NonEuclidean)?;
+
+        // The tiling lives in a space of one dimension less than the diagram.
+        let dim = dim - 1;
+        let distances = self.node_vector() / 2.0;
+
+        // Places the generator at the given distances from the mirrors through
+        // the origin.
+        let mut generator = distances.rows(0, dim).into_owned();
+        if !normals
+            .slice((0, 0), (dim, dim))
+            .tr_solve_upper_triangular_mut(&mut generator)
+        {
+            return Err(CdError::NonEuclidean);
+        }
+
+        // Moves the last mirror so that the generator is at the right distance
+        // from it. This amounts to rescaling the fundamental simplex.
+        let last = normals.column(dim).rows(0, dim).into_owned();
+        let offset = distances[dim] - last.dot(&generator);
+
+        let mut mirrors: Vec<_> = (0..dim)
+            .map(|i| (normals.column(i).rows(0, dim).into_owned(), 0.0))
+            .collect();
+        mirrors.push((last, offset));
+
+        let (vertices, perms) = orbit(generator, &mirrors, Some(radius));
+        Ok(self.build_with(vertices, &perms))
+    }
+
+    /// Builds the elements of a Wythoffian polytope from the orbit of its
+    /// generator, and the partial permutations of it induced by each mirror.
+    /// Elements with a vertex outside of the orbit are skipped.
+    fn build_with(&self, vertices: Vec<Point<f64>>, perms: &[Vec<Option<usize>>]) -> Concrete {
+        let dim = self.dim();
+        let vertex_count = vertices.len();
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 1);
+        builder.push_min();
+        builder.push_vertices(vertex_count);
+
+        // Each face is stored as the sorted list of its vertices.
+        let mut prev_faces: Vec<Vec<usize>> = (0..vertex_count).map(|v| vec![v]).collect();
+
+        for k in 1..dim {
+            let mut faces: Vec<Vec<usize>> = Vec::new();
+            let mut face_idx = HashMap::new();
+
+            for mask in (0..1usize << dim).filter(|mask| mask.count_ones() as usize == k) {
+                if !self.is_face_type(mask) {
+                    continue;
+                }
+
+                // The base face is the orbit of the first vertex under the
+                // mirrors in the mask.
+                let mut base = vec![0];
+                let mut complete = true;
+                let mut i = 0;
+                while i < base.len() {
+                    for (j, perm) in perms.iter().enumerate() {
+                        if mask & (1 << j) != 0 {
+                            match perm[base[i]] {
+                                Some(v) => {
+                                    if !base.contains(&v) {
+                                        base.push(v);
+                                    }
+                                }
+                                None => complete = false,
+                            }
+                        }
+                    }
+
+                    i += 1;
+                }
+                base.sort_unstable();
+
+                if !complete || face_idx.contains_key(&base) {
+                    continue;
+                }
+
+                // Finds the orbit of the base face under the whole group.
+                let start = faces.len();
+                face_idx.insert(base.clone(), start);
+                faces.push(base);
+
+                let mut i = start;
+                while i < faces.len() {
+                    for perm in perms {
+                        let image: Option<Vec<usize>> = faces[i].iter().map(|&v| perm[v]).collect();
+
+                        if let Some(mut image) = image {
+                            image.sort_unstable();
+
+                            if !face_idx.contains_key(&image) {
+                                face_idx.insert(image.clone(), faces.len());
+                                faces.push(image);
+                            }
+                        }
+                    }
+
+                    i += 1;
+                }
+            }
+
+            // The subelements of a face are the faces of the previous rank
+            // whose vertices it contains.
+            let mut incidences = vec![Vec::new(); vertex_count];
+            for (i, face) in prev_faces.iter().enumerate() {
+                for &v in face {
+                    incidences[v].push(i);
+                }
+            }
+
+            let mut subelements = SubelementList::with_capacity(faces.len());
+            for face in &faces {
+                let mut subs: Vec<usize> = face
+                    .iter()
+                    .flat_map(|&v| incidences[v].iter().copied())
+                    .filter(|&i| is_subset(&prev_faces[i], face))
+                    .collect();
+                subs.sort_unstable();
+                subs.dedup();
+
+                subelements.push(subs.into());
+            }
+
+            builder.push(subelements);
+            prev_faces = faces;
+        }
+
+        builder.push_max();
+
+        // Safety: the faces of a convex Wythoffian polytope are determined by
+        // their vertices, so we've built a valid polytope. Tiling patches
+        // aren't valid polytopes, but they're still well-formed ranked posets.
+        let abs = unsafe { builder.build() };
+        Concrete::new(vertices, abs)
+    }
+}
+
+impl Compound {
+    /// Checks that all of the components have the same dimension.
+    fn check_dims(&self) -> CdResult<()> {
+        if let Some(first) = self.components.first() {
+            for cd in &self.components {
+                if cd.dim() != first.dim() {
+                    return Err(CdError::DimensionMismatch {
+                        first: first.dim(),
+                        other: cd.dim(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the compound described by the diagram can be built by
+    /// [`Self::build`]. This is much cheaper than building it.
+    pub fn check(&self) -> CdResult<()> {
+        self.check_dims()?;
+        self.components.iter().try_for_each(Cd::check)
+    }
+
+    /// Builds the compound of the Wythoffian polytopes described by each of
+    /// the components. See [`Cd::build`] for more details.
+    ///
+    /// Returns a [`CdError::DimensionMismatch`] if not all of the components
+    /// have the same dimension.
+    pub fn build(&self) -> CdResult<Concrete> {
+        self.check_dims()?;
+
+        let components = self
+            .components
+            .iter()
+            .map(Cd::build)
+            .collect::<CdResult<Vec<_>>>()?;
+
+        Ok(Concrete::compound(components.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, conc::ConcretePolytope, float::Float};
+
+    use vec_like::VecLike;
+
+    /// Builds a polytope from a diagram and checks its element counts.
+    fn test(diagram: &str, el_counts: &[usize]) -> Concrete {
+        let poly = Cd::parse(diagram).unwrap().build().unwrap();
+        crate::test(&poly, el_counts.iter().copied());
+        poly
+    }
+
+    #[test]
+    fn cube() {
+        let cube = test("x4o3o", &[1, 8, 12, 6, 1]);
+        assert!(cube.is_equilateral_with(1.0));
+    }
+
+    #[test]
+    fn tetrahedron() {
+        test("x3o3o", &[1, 4, 6, 4, 1]);
+    }
+
+    #[test]
+    fn octahedron() {
+        test("o3x3o", &[1, 6, 12, 8, 1]);
+    }
+
+    #[test]
+    fn hexagon() {
+        let hexagon = test("x3x", &[1, 6, 6, 1]);
+        assert!(hexagon.is_equilateral_with(1.0));
+    }
+
+    #[test]
+    fn pentagram() {
+        let pentagram = test("x5/2o", &[1, 5, 5, 1]);
+        assert!(pentagram.is_equilateral_with(1.0));
+
+        let radius = Cd::parse("x5/2o").unwrap().circumradius().unwrap();
+        let expected = 1.0 / (2.0 * (f64::TAU / 5.0).fsin());
+        assert!((radius - expected).abs() < f64::EPS);
+    }
+
+    #[test]
+    fn great_rhombicuboctahedron() {
+        test("x4x3x", &[1, 48, 72, 26, 1]);
+    }
+
+    #[test]
+    fn stella_octangula() {
+        let stella = Compound::parse("x3o3o + o3o3x").unwrap().build().unwrap();
+        assert_eq!(stella.el_count(1), 8);
+        assert_eq!(stella.el_count(2), 12);
+        assert_eq!(stella.el_count(3), 8);
+    }
+
+    #[test]
+    fn compound_mismatch() {
+        assert!(matches!(
+            Compound::parse("x3o3o + x").unwrap().build(),
+            Err(CdError::DimensionMismatch { first: 3, other: 1 })
+        ));
+    }
+
+    #[test]
+    fn non_spherical() {
+        assert!(matches!(
+            Cd::parse("x4o4o").unwrap().build(),
+            Err(CdError::NonSpherical)
+        ));
+    }
+
+    /// Checks that diagrams are validated without being built.
+    #[test]
+    fn check() {
+        assert!(Compound::parse("x4o3o + o3o4x").unwrap().check().is_ok());
+        assert!(Cd::parse("x").unwrap().check().is_ok());
+
+        assert!(matches!(
+            Cd::parse("x4o4o").unwrap().check(),
+            Err(CdError::NonSpherical)
+        ));
+        assert!(matches!(
+            Cd::parse("o3o3x o").unwrap().check(),
+            Err(CdError::NonMinimal)
+        ));
+        assert!(matches!(
+            Cd::parse("s4o3o").unwrap().check(),
+            Err(CdError::SnubNode { idx: 0 })
+        ));
+        assert!(matches!(
+            Compound::parse("x3o3o + x").unwrap().check(),
+            Err(CdError::DimensionMismatch { first: 3, other: 1 })
+        ));
+    }
+
+    /// Checks the patch of the square tiling within a radius of 3.
+    #[test]
+    fn square_tiling() {
+        let tiling = Cd::parse("x4o4o").unwrap().build_tiling(3.0).unwrap();
+        assert_eq!(tiling.vertex_count(), 32);
+        assert_eq!(tiling.el_count(3), 21);
+        assert!(tiling.is_equilateral_with(1.0));
+
+        for square in &tiling[3] {
+            assert_eq!(square.subs.len(), 4);
+        }
+    }
+
+    /// Checks that disconnected diagrams build duoprisms, but not tilings.
+    #[test]
+    fn disconnected() {
+        let cd = Cd::parse("x3o x3o").unwrap();
+        assert!(!cd.is_connected());
+        assert_eq!(cd.second_component(), Some(2));
+        assert!(matches!(
+            cd.build_tiling(3.0),
+            Err(CdError::Disconnected { idx: 2 })
+        ));
+
+        test("x3o x3o", &[1, 9, 18, 15, 6, 1]);
+        assert!(Cd::parse("x4o4o").unwrap().is_connected());
+    }
+
+    #[test]
+    fn non_euclidean() {
+        for diagram in ["x4o3o", "x4o5o", "x"] {
+            assert!(matches!(
+                Cd::parse(diagram).unwrap().build_tiling(3.0),
+                Err(CdError::NonEuclidean)
+            ));
+        }
+    }
+
+    #[test]
+    fn circumradius() {
+        let cd = Cd::parse("x4o3o").unwrap();
+        let radius = cd.circumradius().unwrap();
+        assert!((radius - 3f64.fsqrt() / 2.0).abs() < f64::EPS);
+    }
+}
@@ -1,125 +1,24 @@
-//! Contains the code that opens a GGB file and parses it into a polytope.
+//! Contains the code that reads and writes GGB files.
 
-// This code is unfinished.
-#![allow(dead_code)]
-#![allow(clippy::collapsible_match)]
-
-use std::io::Result as IoResult;
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read},
+};
 
-use crate::{conc::Concrete, geometry::Point};
+use crate::{
+    abs::{AbstractBuilder, Ranked, SubelementList, Subelements},
+    conc::{cycle::CycleList, Concrete},
+    geometry::Point,
+};
 
 use nalgebra::dvector;
+use vec_like::VecLike;
 use xml::{
     attribute::OwnedAttribute,
     reader::{EventReader, XmlEvent},
 };
 use zip::result::ZipError;
 
-type Events<'a> = xml::reader::Events<&'a [u8]>;
-
-/// A wrapper around an iterator over events in an XML file.
-pub struct XmlReader<'a>(Events<'a>);
-
-impl<'a> Iterator for XmlReader<'a> {
-    type Item = xml::reader::Result<XmlEvent>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
-    }
-}
-
-impl<'a> AsRef<Events<'a>> for XmlReader<'a> {
-    fn as_ref(&self) -> &Events<'a> {
-        &self.0
-    }
-}
-
-impl<'a> AsMut<Events<'a>> for XmlReader<'a> {
-    fn as_mut(&mut self) -> &mut Events<'a> {
-        &mut self.0
-    }
-}
-
-impl<'a> XmlReader<'a> {
-    /// Initializes a new XML reader from a source XML string.
-    pub fn new(xml: &'a str) -> Self {
-        Self(EventReader::from_str(xml).into_iter())
-    }
-
-    /// Reads an XML file until an XML element with a given name is found.
-    /// Returns its attributes.
-    fn read_until(&mut self, search: &str) -> GgbResult<Vec<OwnedAttribute>> {
-        for xml_result in self.as_mut() {
-            match xml_result {
-                // The next XML event to process:
-                Ok(xml_event) => {
-                    // If we have an element and it has the correct name, we return
-                    // this XML event.
-                    if let XmlEvent::StartElement {
-                        ref name,
-                        attributes,
-                        ..
-                    } = xml_event
-                    {
-                        if name.local_name == search {
-                            return Ok(attributes);
-                        }
-                    } else {
-                        todo!()
-                    }
-                }
-                // Something went wrong while fetching the next XML event.
-                Err(_) => return Err(GgbError::InvalidXml),
-            }
-        }
-
-        // We didn't find the element we were looking for.
-        Err(GgbError::MissingElement)
-    }
-
-    /// Reads a point from the GGB file, assuming that we're currently in an XML
-    /// label of the form
-    /// ```xml
-    /// <element type="point3d" label="A">
-    /// ```
-    fn read_point(&mut self, attributes: &[OwnedAttribute]) -> GgbResult<Vertex<f64>> {
-        let label = attribute(attributes, "label").unwrap_or_default();
-        let coord_attributes = self.read_until("coords")?;
-
-        /// Reads any of the coordinates of a point, saves it in a variable with
-        /// the same name.
-        macro_rules! read_coord {
-            ($x:ident) => {
-                let $x: f64;
-
-                if let Some(c) = attribute(&coord_attributes, stringify!($x)) {
-                    if let Ok(c) = c.parse() {
-                        $x = c;
-                    } else {
-                        return Err(GgbError::ParseError);
-                    };
-                } else {
-                    return Err(GgbError::MissingAttribute);
-                }
-            };
-        }
-
-        read_coord!(x);
-        read_coord!(y);
-        read_coord!(z);
-        read_coord!(w);
-
-        Ok(Vertex {
-            coords: dvector![x / w, y / w, z / w],
-            label: label.to_string(),
-        })
-    }
-
-    fn read_edge(&self) -> IoResult<Edge> {
-        todo!()
-    }
-}
-
 /// Possible errors while reading a GGB file.
 #[derive(Debug)]
 pub enum GgbError {
@@ -140,6 +39,10 @@ pub enum GgbError {
 
     /// Some number could not be parsed.
     ParseError,
+
+    /// The GGB file contains curves, or something else that can't be read as
+    /// a polyhedron.
+    Unsupported,
 }
 
 impl std::fmt::Display for GgbError {
@@ -151,6 +54,7 @@ impl std::fmt::Display for GgbError {
             Self::InvalidGgb => write!(f, "invalid GGB"),
             Self::ZipError(err) => write!(f, "ZIP error: {}", err),
             Self::ParseError => write!(f, "parse error"),
+            Self::Unsupported => write!(f, "unsupported GGB construction"),
         }
     }
 }
@@ -166,9 +70,23 @@ pub type GgbResult<T> = Result<T, GgbError>;
 
 impl std::error::Error for GgbError {}
 
-enum Element {
-    Point3D { label: String },
-}
+/// The GeoGebra element types that can't be read as part of a polyhedron.
+const UNSUPPORTED_TYPES: &[&str] = &[
+    "conic",
+    "conic3d",
+    "conicpart",
+    "curvecartesian",
+    "curvecartesian3d",
+    "function",
+    "functionNVar",
+    "implicitpoly",
+    "implicitsurface3d",
+    "locus",
+    "quadric",
+    "quadricpart",
+    "quadriclimited",
+    "surfacecartesian3d",
+];
 
 /// Returns the value of an attribute with a given name in an XML element.
 ///
@@ -185,73 +103,392 @@ fn attribute<'a>(attributes: &'a [OwnedAttribute], idx: &str) -> Option<&'a str>
     None
 }
 
-/// A vertex in a GGB file.
-#[derive(Debug)]
-struct Vertex<T> {
-    /// The coordinates of the vertex.
-    coords: Point<T>,
-
-    /// The name of the vertex.
-    label: String,
+/// Reads a coordinate with a given name from the attributes of a `coords` XML
+/// element.
+fn coord(attributes: &[OwnedAttribute], name: &str) -> GgbResult<f64> {
+    attribute(attributes, name)
+        .ok_or(GgbError::MissingAttribute)?
+        .parse()
+        .map_err(|_| GgbError::ParseError)
 }
 
-struct Edge {
-    label: String,
-}
+/// The GeoGebra object whose XML we're currently reading.
+enum Current {
+    /// A point with a given label.
+    Point {
+        /// The label of the point.
+        label: String,
 
-struct Face {
-    vertices: Vec<String>,
-    edges: Vec<String>,
-}
+        /// Whether this is a 3D point.
+        is_3d: bool,
+    },
 
-fn read_face() -> Face {
-    todo!()
+    /// A polygon command.
+    Polygon,
+
+    /// Anything else.
+    Other,
 }
 
 /// Parses the `geogebra.xml` file to produce a polytope.
+///
+/// Every point is read as a vertex, and every `Polygon` command whose inputs
+/// are points is read as a face. Points that aren't used by any polygon are
+/// ignored.
 pub(super) fn parse_xml(xml: &str) -> GgbResult<Concrete> {
-    let mut vertices: Vec<Vertex<f64>> = Vec::new();
-    let mut edges = Vec::new();
-    let mut xml = XmlReader::new(xml);
-
-    loop {
-        match xml.as_mut().next() {
-            // If the document isn't yet over:
-            Some(xml_result) => match xml_result {
-                // The next XML event to process:
-                Ok(xml_event) => {
-                    if let XmlEvent::StartElement {
-                        name, attributes, ..
-                    } = xml_event
+    let mut vertices = Vec::new();
+    let mut labels = HashMap::new();
+    let mut polygons = Vec::new();
+    let mut current = Current::Other;
+
+    for xml_result in EventReader::from_str(xml) {
+        let (name, attributes) = match xml_result {
+            Ok(XmlEvent::StartElement {
+                name, attributes, ..
+            }) => (name.local_name, attributes),
+            Ok(_) => continue,
+
+            // Something went wrong while fetching the next XML event.
+            Err(_) => return Err(GgbError::InvalidXml),
+        };
+
+        match name.as_str() {
+            // We found a GeoGebra object.
+            "element" => {
+                let el_type = attribute(&attributes, "type").ok_or(GgbError::MissingAttribute)?;
+                if UNSUPPORTED_TYPES.contains(&el_type) {
+                    return Err(GgbError::Unsupported);
+                }
+
+                current = match el_type {
+                    "point" | "point3d" => Current::Point {
+                        label: attribute(&attributes, "label")
+                            .ok_or(GgbError::MissingAttribute)?
+                            .to_owned(),
+                        is_3d: el_type == "point3d",
+                    },
+                    _ => Current::Other,
+                };
+            }
+
+            // We found a GeoGebra command.
+            "command" => {
+                current = match attribute(&attributes, "name") {
+                    Some("Polygon") => Current::Polygon,
+                    Some(_) => Current::Other,
+                    None => return Err(GgbError::MissingAttribute),
+                };
+            }
+
+            // We found the coordinates of a point.
+            "coords" => {
+                if let Current::Point { label, is_3d } = &current {
+                    let x = coord(&attributes, "x")?;
+                    let y = coord(&attributes, "y")?;
+                    let z = coord(&attributes, "z")?;
+
+                    // 2D points store their homogeneous coordinate in z.
+                    let point = if *is_3d {
+                        let w = match attribute(&attributes, "w") {
+                            Some(_) => coord(&attributes, "w")?,
+                            None => 1.0,
+                        };
+                        dvector![x / w, y / w, z / w]
+                    } else {
+                        dvector![x / z, y / z, 0.0]
+                    };
+
+                    labels.insert(label.clone(), vertices.len());
+                    vertices.push(point);
+                    current = Current::Other;
+                }
+            }
+
+            // We found the inputs of a polygon.
+            "input" => {
+                if let Current::Polygon = current {
+                    let mut polygon = Vec::new();
+                    while let Some(label) =
+                        attribute(&attributes, &format!("a{}", polygon.len()))
                     {
-                        let name = name.local_name;
-
-                        if name == "element" {
-                            if let Some(el_type) = attribute(&attributes, "type") {
-                                // We found a point.
-                                if el_type == "point3d" {
-                                    vertices.push(xml.read_point(&attributes)?);
-                                }
-                                // We found an edge.
-                                else if el_type == "segment3d" {
-                                    if let Ok(edge) = xml.read_edge() {
-                                        edges.push(edge);
-                                    }
-                                }
-                            }
-                        }
-
-                        /*  "element" => read_edge(&mut xml),
-                        "command" => read_face(&mut xml), */
+                        polygon.push(label.to_owned());
                     }
+
+                    polygons.push(polygon);
+                    current = Current::Other;
                 }
+            }
 
-                // Something went wrong while fetching the next XML event.
-                Err(_) => return Err(GgbError::InvalidXml),
-            },
+            _ => {}
+        }
+    }
 
-            // The file has finished being read. Time for processing!
-            None => todo!(),
+    build_polyhedron(vertices, &labels, &polygons)
+}
+
+/// Builds a polyhedron out of its vertices and its polygons, given as lists
+/// of vertex labels.
+fn build_polyhedron(
+    vertices: Vec<Point<f64>>,
+    labels: &HashMap<String, usize>,
+    polygons: &[Vec<String>],
+) -> GgbResult<Concrete> {
+    if polygons.is_empty() {
+        return Err(GgbError::Unsupported);
+    }
+
+    // Polygons given by anything other than a list of points aren't supported.
+    let polygons = polygons
+        .iter()
+        .map(|polygon| {
+            if polygon.len() < 3 {
+                return Err(GgbError::Unsupported);
+            }
+
+            polygon
+                .iter()
+                .map(|label| labels.get(label).copied().ok_or(GgbError::Unsupported))
+                .collect::<GgbResult<Vec<_>>>()
+        })
+        .collect::<GgbResult<Vec<_>>>()?;
+
+    // Only keeps the vertices that belong to some polygon.
+    let mut vertex_map = vec![None; vertices.len()];
+    let mut new_vertices = Vec::new();
+    for &v in polygons.iter().flatten() {
+        if vertex_map[v].is_none() {
+            vertex_map[v] = Some(new_vertices.len());
+            new_vertices.push(vertices[v].clone());
+        }
+    }
+
+    let mut edge_idx = HashMap::new();
+    let mut edges = SubelementList::new();
+    let mut edge_faces = Vec::new();
+    let mut faces = SubelementList::with_capacity(polygons.len());
+
+    for polygon in &polygons {
+        let len = polygon.len();
+        let mut subs = Subelements::with_capacity(len);
+
+        for i in 0..len {
+            let (a, b) = (polygon[i], polygon[(i + 1) % len]);
+            let (a, b) = (vertex_map[a].unwrap(), vertex_map[b].unwrap());
+            if a == b {
+                return Err(GgbError::InvalidGgb);
+            }
+
+            let key = (a.min(b), a.max(b));
+            let idx = *edge_idx.entry(key).or_insert_with(|| {
+                edges.push(vec![key.0, key.1].into());
+                edge_faces.push(0);
+                edges.len() - 1
+            });
+
+            edge_faces[idx] += 1;
+            subs.push(idx);
+        }
+
+        faces.push(subs);
+    }
+
+    // Every edge of a polyhedron must be shared by exactly two faces.
+    if edge_faces.iter().any(|&count| count != 2) {
+        return Err(GgbError::InvalidGgb);
+    }
+
+    let mut builder = AbstractBuilder::with_rank_capacity(4);
+    builder.push_min();
+    builder.push_vertices(new_vertices.len());
+    builder.push(edges);
+    builder.push(faces);
+    builder.push_max();
+
+    // Safety: every edge joins two distinct vertices and is shared by exactly
+    // two faces, so we've built a valid polyhedron.
+    let abs = unsafe { builder.build() };
+    Ok(Concrete::new(new_vertices, abs))
+}
+
+/// Reads a polytope from the bytes of a GGB file, i.e. a ZIP archive
+/// containing a `geogebra.xml` file.
+pub(super) fn read_ggb(bytes: &[u8]) -> GgbResult<Concrete> {
+    let mut xml = String::new();
+    zip::ZipArchive::new(Cursor::new(bytes))?
+        .by_name("geogebra.xml")?
+        .read_to_string(&mut xml)
+        .map_err(|_| GgbError::InvalidGgb)?;
+
+    parse_xml(&xml)
+}
+
+/// The header of the `geogebra.xml` file in a GGB file created with Miratope.
+const XML_HEADER: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+    "<geogebra format=\"5.0\">\n",
+    "<construction title=\"Generated using Miratope\">\n"
+);
+
+/// The footer of the `geogebra.xml` file in a GGB file created with Miratope.
+const XML_FOOTER: &str = "</construction>\n</geogebra>\n";
+
+/// Returns the label for the vertex with a given index.
+fn vertex_label(idx: usize) -> String {
+    format!("V_{{{}}}", idx)
+}
+
+impl Concrete {
+    /// Writes the `geogebra.xml` file for a polytope. Vertices are written as
+    /// points, and every 2-element is written as one or more polygons,
+    /// depending on the number of its components. Any coordinates past the
+    /// third are ignored, so higher-dimensional polytopes get projected into
+    /// 3D.
+    pub fn to_ggb_xml(&self) -> String {
+        use std::fmt::Write;
+
+        let mut xml = String::from(XML_HEADER);
+
+        // Writes the vertices.
+        for (idx, v) in self.vertices.iter().enumerate() {
+            let coord = |i: usize| v.get(i).copied().unwrap_or_default();
+
+            // Writing into a string never fails.
+            let _ = write!(
+                xml,
+                "<element type=\"point3d\" label=\"{}\">\n\
+                 \t<coords x=\"{}\" y=\"{}\" z=\"{}\" w=\"1\"/>\n\
+                 </element>\n",
+                vertex_label(idx),
+                coord(0),
+                coord(1),
+                coord(2)
+            );
+        }
+
+        // Writes the faces, splitting compound faces into their components.
+        if self.rank() >= 3 {
+            let mut face_count = 0;
+
+            for face in &self[3] {
+                for cycle in CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs)) {
+                    xml.push_str("<command name=\"Polygon\">\n\t<input");
+                    for (i, &v) in cycle.iter().enumerate() {
+                        let _ = write!(xml, " a{}=\"{}\"", i, vertex_label(v));
+                    }
+                    let _ = write!(
+                        xml,
+                        "/>\n\t<output a0=\"F_{{{}}}\"/>\n</command>\n",
+                        face_count
+                    );
+
+                    face_count += 1;
+                }
+            }
         }
+
+        xml.push_str(XML_FOOTER);
+        xml
+    }
+
+    /// Converts a polytope into a GGB file, i.e. a ZIP archive containing a
+    /// single `geogebra.xml` file. See [`Self::to_ggb_xml`] for details.
+    pub fn to_ggb(&self) -> GgbResult<Vec<u8>> {
+        use std::io::{Cursor, Write};
+
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("geogebra.xml", zip::write::FileOptions::default())?;
+        zip.write_all(self.to_ggb_xml().as_bytes())
+            .map_err(ZipError::from)?;
+
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_xml, GgbError};
+    use crate::{abs::Ranked, conc::Concrete, file::FromFile, Polytope};
+
+    use std::io::{Cursor, Read};
+
+    /// Reads back the `geogebra.xml` file from the GGB file of a polytope.
+    fn ggb_xml(poly: &Concrete) -> String {
+        let ggb = poly.to_ggb().expect("GGB file could not be written.");
+        let mut archive = zip::ZipArchive::new(Cursor::new(ggb)).unwrap();
+        let mut xml = String::new();
+        archive
+            .by_name("geogebra.xml")
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    }
+
+    /// Checks that the GGB file of a polytope has the expected number of
+    /// points and polygons.
+    fn test_ggb(poly: &Concrete, points: usize, polygons: usize) {
+        let xml = ggb_xml(poly);
+        assert_eq!(xml.matches("type=\"point3d\"").count(), points);
+        assert_eq!(xml.matches("name=\"Polygon\"").count(), polygons);
+    }
+
+    /// Checks a pentagon.
+    #[test]
+    fn pentagon() {
+        test_ggb(&Concrete::polygon(5), 5, 1)
+    }
+
+    /// Checks a cube.
+    #[test]
+    fn cube() {
+        test_ggb(&Concrete::cube(), 8, 6)
+    }
+
+    /// Checks that a tesseract gets projected into 3D.
+    #[test]
+    fn tesseract() {
+        test_ggb(&Concrete::hypercube(5), 16, 24)
+    }
+
+    /// Checks that a cube can be read from a GGB file.
+    #[test]
+    fn import_cube() {
+        let cube = Concrete::from_ggb(include_bytes!("cube.ggb")).unwrap();
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+    }
+
+    /// Checks that an exported polyhedron can be read back.
+    #[test]
+    fn round_trip() {
+        let ggb = Concrete::polygon(5).prism().to_ggb().unwrap();
+        let prism = Concrete::from_ggb(&ggb).unwrap();
+        crate::test(&prism, [1, 10, 15, 7, 1]);
+    }
+
+    /// Checks that exporting and reimporting a few polyhedra preserves their
+    /// element counts.
+    #[test]
+    fn round_trip_counts() {
+        for poly in [
+            Concrete::tetrahedron(),
+            Concrete::cube(),
+            Concrete::octahedron(),
+            Concrete::polygon(6).pyramid(),
+        ] {
+            let ggb = poly.to_ggb().unwrap();
+            let imported = Concrete::from_ggb(&ggb).unwrap();
+            imported.assert_valid();
+            assert_eq!(imported.abs().el_counts(), poly.abs().el_counts());
+        }
+    }
+
+    /// Checks that curves aren't supported.
+    #[test]
+    fn unsupported() {
+        let xml = "<geogebra><construction>\
+            <element type=\"conic3d\" label=\"c\"/>\
+            </construction></geogebra>";
+
+        assert!(matches!(parse_xml(xml), Err(GgbError::Unsupported)));
     }
 }
@@ -13,7 +13,7 @@ use off::OffParseError;
 use zip::result::ZipError;
 
 pub use std::io::Error as IoError;
-use std::{fs::File, string::FromUtf8Error, fmt::Display};
+use std::{string::FromUtf8Error, fmt::Display};
 
 /// Any error encountered while trying to load a polytope.
 #[derive(Debug)]
@@ -99,9 +99,13 @@ pub trait FromFile: Sized {
     /// Maybe don't load the entire file at once?
     fn from_off(src: &str) -> OffParseResult<Self>;
 
-    /// Attempts to read a GGB file. If succesful, outputs a polytope in at most
-    /// 3D.
-    fn from_ggb(file: File) -> GgbResult<Self>;
+    /// Attempts to read the bytes of a GGB file. If succesful, outputs a
+    /// polyhedron.
+    ///
+    /// Only polyhedra made out of points and polygons are supported. Returns
+    /// [`GgbError::Unsupported`] if the file contains curves or anything else
+    /// that can't be read as a polyhedron.
+    fn from_ggb(bytes: &[u8]) -> GgbResult<Self>;
 
     /// Loads a polytope from a file path.
     fn from_path<U: AsRef<std::path::Path>>(fp: &U) -> FileResult<'_, Self> {
@@ -121,7 +125,7 @@ pub trait FromFile: Sized {
             },
 
             // Reads the file as a GGB file.
-            "ggb" => Ok(Self::from_ggb(fs::File::open(fp)?)?),
+            "ggb" => Ok(Self::from_ggb(&fs::read(fp)?)?),
 
             // Could not recognize the file extension.
             ext => Err(FileError::InvalidExtension(ext)),
@@ -134,22 +138,8 @@ impl FromFile for Concrete {
         OffReader::new(src).build()
     }
 
-    /// Attempts to read a GGB file. If succesful, outputs a polytope in at most
-    /// 3D.
-    fn from_ggb(mut file: File) -> GgbResult<Self> {
-        use std::io::Read;
-
-        if let Ok(xml) = String::from_utf8(
-            zip::read::ZipArchive::new(&mut file)?
-                .by_name("geogebra.xml")?
-                .bytes()
-                .map(|b| b.unwrap_or(0))
-                .collect(),
-        ) {
-            ggb::parse_xml(&xml)
-        } else {
-            Err(GgbError::InvalidGgb)
-        }
+    fn from_ggb(bytes: &[u8]) -> GgbResult<Self> {
+        ggb::read_ggb(bytes)
     }
 }
 
@@ -924,6 +924,12 @@ mod tests {
         test_off!("tet", [1, 4, 6, 4, 1])
     }
 
+    /// Checks that a cube has the correct amount of elements.
+    #[test]
+    fn cube_nums() {
+        test_off!("cube", [1, 8, 12, 6, 1])
+    }
+
     /// Checks that a 2-tetrahedron compund has the correct amount of elements.
     #[test]
     fn so_nums() {
@@ -942,6 +948,26 @@ mod tests {
         test_off!("comments", [1, 4, 6, 4, 1])
     }
 
+    /// Exports a polytope without comments, checks the header of the file,
+    /// and checks that reloading it gives back the same element counts.
+    fn test_export(poly: Concrete, header: &str) {
+        let off = poly.to_off(OffOptions { comments: false }).unwrap();
+        assert!(off.starts_with(header), "wrong OFF header in {:?}", off);
+        test(&Concrete::from_off(&off).unwrap(), poly.el_count_iter());
+    }
+
+    /// Checks that exporting works for all ranks.
+    #[test]
+    fn export() {
+        test_export(Concrete::nullitope(), "-1OFF\n");
+        test_export(Concrete::point(), "0OFF\n");
+        test_export(Concrete::dyad(), "1OFF\n");
+        test_export(Concrete::polygon(5), "2OFF\n");
+        test_export(Concrete::hypercube(4), "OFF\n");
+        test_export(Concrete::hypercube(5), "4OFF\n");
+        test_export(Concrete::hypercube(6), "5OFF\n");
+    }
+
     /// Attempts to parse an OFF file, unwraps it.
     fn unwrap_off(src: &str) {
         Concrete::from_off(src).unwrap();
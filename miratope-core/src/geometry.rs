@@ -71,12 +71,19 @@ impl<T: Float> Hypersphere<T> {
     /// Attempts to reciprocate a point in place. If it's too close to the
     /// sphere's center, it returns `false` and leaves it unchanged.
     pub fn reciprocate_mut(&self, p: &mut Point<T>) -> bool {
+        self.reciprocate_mut_with_tol(p, T::EPS.fsqrt())
+    }
+
+    /// Attempts to reciprocate a point in place. If its distance to the
+    /// sphere's center is less than a given tolerance, it returns `false` and
+    /// leaves it unchanged.
+    pub fn reciprocate_mut_with_tol(&self, p: &mut Point<T>, tol: T) -> bool {
         let mut q = (p as &Point<T>) - &self.center;
         let s = q.norm_squared();
 
         // If any face passes through the dual center, the dual does
         // not exist, and we return early.
-        if s < T::EPS {
+        if s < tol * tol {
             return false;
         }
 
@@ -92,6 +99,19 @@ impl<T: Float> Hypersphere<T> {
         self.reciprocate_mut(&mut p).then(|| p)
     }
 
+    /// Inverts a point through the hypersphere, or returns `None` if it's the
+    /// center of the hypersphere. This is the same as
+    /// [reciprocating](Self::reciprocate) it.
+    pub fn invert_point(&self, p: &Point<T>) -> Option<Point<T>> {
+        self.reciprocate(p.clone())
+    }
+
+    /// Inverts a set of points through the hypersphere, or returns `None` if
+    /// any of them is the center of the hypersphere.
+    pub fn invert_points(&self, points: &[Point<T>]) -> Option<Vec<Point<T>>> {
+        points.iter().map(|p| self.invert_point(p)).collect()
+    }
+
     /// Calculates the circumsphere of points. Returns `None` if the
     /// points aren't circumscribable.
     pub fn circumsphere(points: &Vec<Point<T>>) -> Option<Hypersphere<T>> {
@@ -392,6 +412,22 @@ impl<'a, T: Float> Segment<'a, T> {
     }
 }
 
+/// Returns the matrix of a rotation by a given angle in the plane spanned by
+/// two coordinate axes, from the first axis towards the second.
+///
+/// # Panics
+/// Panics if either axis is out of bounds.
+pub fn rotation<T: Float>(dim: usize, i: usize, j: usize, angle: T) -> Matrix<T> {
+    let (sin, cos) = angle.fsin_cos();
+    let mut m = Matrix::identity(dim, dim);
+
+    m[(i, i)] = cos;
+    m[(j, j)] = cos;
+    m[(i, j)] = -sin;
+    m[(j, i)] = sin;
+    m
+}
+
 /// A matrix ordered by fuzzy lexicographic ordering. That is, lexicographic
 /// ordering where two entries that differ by less than an epsilon are
 /// considered equal.
@@ -525,6 +561,32 @@ mod tests {
         assert_abs_diff_eq!((p - q).norm(), 0.0, epsilon = f32::EPS)
     }
 
+    #[test]
+    /// Inverts points through spheres.
+    pub fn invert() {
+        let sphere = Hypersphere::unit(3);
+        let p = dvector![2.0, 0.0, 0.0];
+        assert_eq(sphere.invert_point(&p).unwrap(), dvector![0.5, 0.0, 0.0]);
+
+        let q = dvector![0.0, 1.2, 1.6];
+        let inverted = sphere.invert_points(&[p, q]).unwrap();
+        assert_eq(inverted[1].clone(), dvector![0.0, 0.3, 0.4]);
+
+        assert!(sphere.invert_point(&Point::zeros(3)).is_none());
+        assert!(sphere.invert_points(&[dvector![1.0, 1.0, 1.0], Point::zeros(3)]).is_none());
+    }
+
+    #[test]
+    /// Rotates points in coordinate planes.
+    pub fn rotate() {
+        let m = rotation(3, 0, 1, f32::PI / 2.0);
+        assert_eq(&m * dvector![1.0, 0.0, 5.0], dvector![0.0, 1.0, 5.0]);
+        assert_eq(&m * dvector![0.0, 1.0, 0.0], dvector![-1.0, 0.0, 0.0]);
+
+        let m = rotation(4, 1, 3, f32::PI);
+        assert_eq(&m * dvector![1.0, 2.0, 3.0, 4.0], dvector![1.0, -2.0, 3.0, -4.0]);
+    }
+
     #[test]
     /// Reciprocates points about spheres.
     pub fn reciprocate() {
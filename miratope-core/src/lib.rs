@@ -39,6 +39,7 @@ use abs::{
     Abstract, Element, ElementList, ElementMap, Ranked,
 };
 
+use conc::element_types::EL_NAMES;
 use vec_like::VecLike;
 
 /// The word "Components".
@@ -57,6 +58,24 @@ impl std::fmt::Display for DualError {
 
 impl Error for DualError {}
 
+/// The number of iterations an expensive operation is allowed to take when no
+/// explicit budget is given.
+pub const DEFAULT_BUDGET: usize = 1 << 24;
+
+/// Represents an error in which an expensive operation, like iterating over
+/// flags or computing a symmetry group, went over a given budget of
+/// iterations.
+#[derive(Clone, Copy, Debug)]
+pub struct BudgetExceeded(pub usize);
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "operation exceeded its budget of {} iterations", self.0)
+    }
+}
+
+impl Error for BudgetExceeded {}
+
 /// Gets the precalculated value for n!.
 fn factorial(n: usize) -> u32 {
     /// Precalculated factorials from 0! to 13!.
@@ -367,6 +386,18 @@ pub trait Polytope:
     /// `None` if this Petrie polygon is invalid.
     fn petrie_polygon_with(&mut self, flag: Flag) -> Option<Self>;
 
+    /// Builds the [Petrie polygon](https://polytope.miraheze.org/wiki/Petrie_polygon)
+    /// of a polytope, by applying every flag change in order to its
+    /// [first flag](Self::first_flag) until we get back to it. Returns `None`
+    /// if the Petrie polygon self-intersects, or if the traversal doesn't
+    /// close back onto the starting flag.
+    fn petrie_polygon(&self) -> Option<Self> {
+        let mut clone = self.clone();
+        clone.element_sort();
+        let flag = clone.first_flag();
+        clone.petrie_polygon_with(flag)
+    }
+
     /// Returns the first [`Flag`] of a polytope. This is the flag built when we
     /// start at the maximal element and repeatedly take the first subelement.
     fn first_flag(&self) -> Flag {
@@ -397,6 +428,92 @@ pub trait Polytope:
         FlagIter::new(self.abs())
     }
 
+    /// Counts the [`Flag`]s of a polytope, or returns an error if there are
+    /// more than a given number of them. If no budget is given, uses
+    /// [`DEFAULT_BUDGET`].
+    fn try_flag_count(&self, budget: Option<usize>) -> Result<usize, BudgetExceeded> {
+        let budget = budget.unwrap_or(DEFAULT_BUDGET);
+        let mut count = 0;
+
+        for _ in self.flags() {
+            if count == budget {
+                return Err(BudgetExceeded(budget));
+            }
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Returns the number of connected components of the facet-adjacency
+    /// graph of a polytope, where two facets are adjacent whenever they share
+    /// a ridge. Polytopes of rank at most 1 have a single component.
+    fn component_count(&self) -> usize {
+        let rank = self.rank();
+        if rank < 2 {
+            return 1;
+        }
+
+        let facet_rank = rank - 1;
+        let facet_count = self.facet_count();
+        let mut visited = vec![false; facet_count];
+        let mut count = 0;
+
+        for start in 0..facet_count {
+            if visited[start] {
+                continue;
+            }
+
+            // Traverses the component of the starting facet.
+            count += 1;
+            visited[start] = true;
+            let mut stack = vec![start];
+
+            while let Some(facet) = stack.pop() {
+                for &ridge in &self[(facet_rank, facet)].subs {
+                    for &other in &self[(facet_rank - 1, ridge)].sups {
+                        if !visited[other] {
+                            visited[other] = true;
+                            stack.push(other);
+                        }
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Determines whether two polytopes are isomorphic, i.e. whether there's a
+    /// bijection between their elements that preserves rank and incidence.
+    /// See [`flag::is_isomorphic`](abs::flag::is_isomorphic) for details.
+    fn is_isomorphic(&self, other: &Self) -> bool {
+        let mut p = self.abs().clone();
+        let mut q = other.abs().clone();
+        p.element_sort();
+        q.element_sort();
+
+        abs::flag::is_isomorphic(&p, &q)
+    }
+
+    /// Returns the number of [`Flag`]s of a polytope. See
+    /// [`Self::try_flag_count`] for a version with a budget.
+    fn flag_count(&self) -> usize {
+        self.flags().count()
+    }
+
+    /// Determines whether a polytope is
+    /// [regular](https://polytope.miraheze.org/wiki/Regular_polytope) as an
+    /// abstract polytope, i.e. whether its automorphism group acts
+    /// transitively on its flags. See
+    /// [`flag::is_flag_transitive`](abs::flag::is_flag_transitive) for details.
+    fn is_regular_abstract(&self) -> bool {
+        let mut p = self.abs().clone();
+        p.element_sort();
+        abs::flag::is_flag_transitive(&p)
+    }
+
     /// Returns an iterator over all [`OrientedFlag`]s of a polytope.
     ///
     /// # Panics
@@ -408,6 +525,32 @@ pub trait Polytope:
     /// Returns the omnitruncate of a polytope.
     fn omnitruncate(&self) -> Self;
 
+    /// Returns the [truncate](https://polytope.miraheze.org/wiki/Truncation)
+    /// of a polytope, whose vertices correspond to the vertex-edge pairs of
+    /// the original polytope. Polytopes of rank less than 3 are returned
+    /// unchanged.
+    fn truncate(&self) -> Self;
+
+    /// Returns the [rectate](https://polytope.miraheze.org/wiki/Rectification)
+    /// of a polytope, whose vertices correspond to the edges of the original
+    /// polytope. Polytopes of rank less than 3 are returned unchanged.
+    fn rectify(&self) -> Self;
+
+    /// Returns the [alternate](https://polytope.miraheze.org/wiki/Alternation)
+    /// of a polytope, which keeps every other vertex. For instance, the
+    /// alternate of a hypercube is a demicube. Returns `None` if the polytope
+    /// can't be alternated, which happens in particular whenever one of its
+    /// faces has an odd number of edges.
+    fn alternate(&self) -> Option<Self>;
+
+    /// Returns the [snub](https://polytope.miraheze.org/wiki/Snub) of a
+    /// polytope, built by [alternating](Self::alternate) its
+    /// [omnitruncate](Self::omnitruncate). Returns `None` if the omnitruncate
+    /// can't be alternated, as happens for non-orientable polytopes.
+    fn snub(&self) -> Option<Self> {
+        self.omnitruncate().alternate()
+    }
+
     /// Builds a [ditope](https://polytope.miraheze.org/wiki/Ditope) of a given
     /// polytope.
     fn ditope(&self) -> Self {
@@ -440,6 +583,16 @@ pub trait Polytope:
     /// Splits compound faces into their components.
     fn untangle_faces(&mut self);
 
+    /// Returns the skeleton of a polytope, consisting of all of its elements of
+    /// rank at most `rank` (using the same convention as [`Ranked::el_count`]),
+    /// together with a new maximal element. For instance, `skeleton(2)` gives
+    /// the vertices and edges of a polytope.
+    ///
+    /// The result has rank `rank + 1`, and in general isn't a valid polytope.
+    /// If `rank` is at least the rank of the facets, we return a copy of the
+    /// polytope, and if it's 0, we return the nullitope.
+    fn skeleton(&self, rank: usize) -> Self;
+
     /// Determines whether a given polytope is
     /// [orientable](https://polytope.miraheze.org/wiki/Orientability).
     ///
@@ -455,6 +608,46 @@ pub trait Polytope:
         self.element_sort();
         self.orientable()
     }
+
+    /// Returns the [Hasse diagram](https://polytope.miraheze.org/wiki/Hasse_diagram)
+    /// of a polytope as a Graphviz DOT digraph. Every element becomes a node
+    /// labeled by its rank and index, and every subelement gets an edge to
+    /// each of its superelements.
+    fn to_dot(&self) -> String {
+        let rank = self.rank();
+        let mut dot = String::from("digraph {\n    rankdir = BT;\n");
+
+        for r in 0..=rank {
+            let name = if r == 0 {
+                "Minimal"
+            } else if r == rank {
+                "Maximal"
+            } else {
+                EL_NAMES.get(r).copied().unwrap_or("")
+            };
+
+            for idx in 0..self.el_count(r) {
+                let label = if name.is_empty() {
+                    format!("{}-element {}", r - 1, idx)
+                } else {
+                    format!("{} {}", name, idx)
+                };
+
+                dot.push_str(&format!("    e{}_{} [label = \"{}\"];\n", r, idx, label));
+            }
+        }
+
+        for r in 1..=rank {
+            for idx in 0..self.el_count(r) {
+                for &sub in &self[(r, idx)].subs {
+                    dot.push_str(&format!("    e{}_{} -> e{}_{};\n", r - 1, sub, r, idx));
+                }
+            }
+        }
+
+        dot.push('}');
+        dot
+    }
 }
 
 /// Returns the multiproduct (i.e. multipyramid, multiprism, multitegum, or
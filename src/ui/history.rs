@@ -0,0 +1,206 @@
+//! Keeps track of the previous states of the polytope on screen, so that
+//! operations on it can be undone and redone.
+
+use std::collections::VecDeque;
+
+use super::main_window::PolyName;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::EguiContext;
+
+/// The maximum number of states stored in the history by default.
+pub const HISTORY_LEN: usize = 32;
+
+/// A bounded undo/redo history of states of some type.
+///
+/// States are pushed into the history right before they're modified. Once the
+/// history is full, the oldest states are forgotten.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    /// The states that can be undone, from oldest to newest.
+    undo: VecDeque<T>,
+
+    /// The states that can be redone, from oldest to newest.
+    redo: Vec<T>,
+
+    /// The maximum number of states that can be undone.
+    capacity: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self::new(HISTORY_LEN)
+    }
+}
+
+impl<T> History<T> {
+    /// Initializes an empty history that stores at most a given number of
+    /// states.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::with_capacity(capacity),
+            redo: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Records a state before it's modified. This clears all states that could
+    /// be redone.
+    pub fn push(&mut self, state: T) {
+        self.redo.clear();
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(state);
+    }
+
+    /// Returns whether there's any state to undo to.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns whether there's any state to redo to.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Replaces the current state by the last recorded one, so that it can be
+    /// redone later. Returns whether there was anything to undo.
+    pub fn undo(&mut self, current: &mut T) -> bool {
+        if let Some(state) = self.undo.pop_back() {
+            self.redo.push(std::mem::replace(current, state));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replaces the current state by the last undone one, so that it can be
+    /// undone again. Returns whether there was anything to redo.
+    pub fn redo(&mut self, current: &mut T) -> bool {
+        if let Some(state) = self.redo.pop() {
+            self.undo.push_back(std::mem::replace(current, state));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The history of the polytope on screen, together with its name.
+pub type PolyHistory = History<(Concrete, String)>;
+
+/// The plugin that stores the history of the polytope on screen and handles
+/// the undo and redo shortcuts.
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PolyHistory>()
+            .add_system(undo_redo.system().after("show_windows"));
+    }
+}
+
+/// Undoes the last operation on Ctrl+Z, and redoes it on Ctrl+Y.
+fn undo_redo(
+    keyboard: Res<'_, Input<KeyCode>>,
+    egui_ctx: Res<'_, EguiContext>,
+    mut history: ResMut<'_, PolyHistory>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut poly_name: ResMut<'_, PolyName>,
+) {
+    // Text fields have their own undo shortcuts.
+    if egui_ctx.ctx().wants_keyboard_input() {
+        return;
+    }
+
+    let ctrl = keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl);
+    if !ctrl {
+        return;
+    }
+
+    let undo = keyboard.just_pressed(KeyCode::Z);
+    let redo = keyboard.just_pressed(KeyCode::Y);
+    if !undo && !redo {
+        return;
+    }
+
+    if let Some(mut polytope) = query.iter_mut().next() {
+        let mut state = (polytope.clone(), std::mem::take(&mut poly_name.0));
+        let changed = if undo {
+            history.undo(&mut state)
+        } else {
+            history.redo(&mut state)
+        };
+
+        let (poly, name) = state;
+        if changed {
+            *polytope = poly;
+        }
+        poly_name.0 = name;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let mut history = History::new(HISTORY_LEN);
+        let mut state = 0;
+
+        assert!(!history.undo(&mut state));
+
+        for i in 1..=3 {
+            history.push(state);
+            state = i;
+        }
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 2);
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 2);
+        assert!(history.can_redo());
+
+        // A new state clears the redo stack.
+        history.push(state);
+        state = 4;
+        assert!(!history.can_redo());
+        assert!(!history.redo(&mut state));
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 2);
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 0);
+        assert!(!history.undo(&mut state));
+        assert_eq!(state, 0);
+    }
+
+    #[test]
+    fn bounded() {
+        let mut history = History::new(2);
+        let mut state = 0;
+
+        for i in 1..=5 {
+            history.push(state);
+            state = i;
+        }
+
+        // Only the last two states are remembered.
+        assert!(history.undo(&mut state));
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 3);
+        assert!(!history.can_undo());
+    }
+}
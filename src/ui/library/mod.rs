@@ -3,7 +3,7 @@
 use std::{
     ffi::{OsStr, OsString},
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use super::{config::LibPath, main_window::PolyName};
@@ -17,6 +17,23 @@ use serde::{Deserialize, Serialize};
 
 mod special;
 
+/// The maximum depth of folders that a search of the library goes into. This
+/// guards against symlink loops.
+const MAX_SEARCH_DEPTH: usize = 16;
+
+/// The extensions of the files that are shown in the library.
+const SUPPORTED_EXTENSIONS: &[&str] = &["off", "ggb"];
+
+/// Returns whether an extension is in a given set of extensions.
+fn extension_in(ext: &OsStr, extensions: &[&str]) -> bool {
+    extensions.iter().any(|&supported| ext == supported)
+}
+
+/// Returns whether files with a given extension are shown in the library.
+fn supported_extension(ext: &OsStr) -> bool {
+    extension_in(ext, SUPPORTED_EXTENSIONS)
+}
+
 /// The plugin that loads the library.
 pub struct LibraryPlugin;
 
@@ -27,7 +44,9 @@ impl Plugin for LibraryPlugin {
 
         // The library must be shown after the top panel, to avoid incorrect
         // positioning.
-        app.insert_resource(library).add_system(
+        app.insert_resource(library)
+            .init_resource::<LibrarySearch>()
+            .add_system(
             show_library
                 .system()
                 .label("show_library")
@@ -85,6 +104,16 @@ impl std::ops::BitOrAssign for ShowResult {
     }
 }
 
+/// The state of the search box on the library.
+#[derive(Default)]
+pub struct LibrarySearch {
+    /// The text in the search box.
+    query: String,
+
+    /// The paths of the files and folders matching the query.
+    results: Vec<PathBuf>,
+}
+
 /// Represents any of the files or folders that make up the Miratope library.
 ///
 /// The library is internally stored is a tree-like structure. Once a folder
@@ -181,11 +210,8 @@ impl Library {
                     contents.push(unloaded_folder);
                 }
                 // Adds a new file.
-                else {
-                    let ext = path.extension();
-                    if ext == Some(OsStr::new("off")) || ext == Some(OsStr::new("ggb")) {
-                        contents.push(Self::new_file(path));
-                    }
+                else if path.extension().map_or(false, supported_extension) {
+                    contents.push(Self::new_file(path));
                 }
             }
 
@@ -193,6 +219,43 @@ impl Library {
         }
     }
 
+    /// Returns the paths of all files and folders in the library whose names
+    /// contain a given query, ignoring case. The library is assumed to be
+    /// located at a given path. Folders that haven't been loaded yet are read
+    /// from disk, though they aren't stored.
+    pub fn search(&self, path: PathBuf, query: &str) -> Vec<PathBuf> {
+        let mut results = Vec::new();
+        self.search_into(&path, &query.to_lowercase(), 0, &mut results);
+        results
+    }
+
+    /// Searches the library recursively, adding the matches to a list.
+    fn search_into(&self, path: &Path, query: &str, depth: usize, results: &mut Vec<PathBuf>) {
+        if self.path_name().to_lowercase().contains(query) {
+            results.push(path.to_path_buf());
+        }
+
+        if depth == MAX_SEARCH_DEPTH {
+            return;
+        }
+
+        let search_contents = |contents: &[Self], results: &mut Vec<PathBuf>| {
+            for lib in contents {
+                lib.search_into(&path.join(lib.path_name()), query, depth + 1, results);
+            }
+        };
+
+        match self {
+            Self::UnloadedFolder { .. } => {
+                if let Ok(contents) = Self::folder_contents(path) {
+                    search_contents(&contents, results);
+                }
+            }
+            Self::LoadedFolder { contents, .. } => search_contents(contents, results),
+            Self::File { .. } | Self::Special(_) => {}
+        }
+    }
+
     /// Shows the library in a given `Ui`, starting from a given path.
     pub fn show(&mut self, ui: &mut Ui, path: PathBuf) -> ShowResult {
         match self {
@@ -243,12 +306,35 @@ impl Library {
     }
 }
 
+/// Shows the results of a search in the library. Files can be loaded by
+/// clicking them.
+fn show_search_results(ui: &mut Ui, results: &[PathBuf]) -> ShowResult {
+    let mut res = ShowResult::None;
+
+    for path in results {
+        let label = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+
+        if path.is_dir() {
+            ui.label(format!("{}/", label));
+        } else if ui.button(label).clicked() {
+            res = ShowResult::Load(path.clone().into_os_string());
+        }
+    }
+
+    if results.is_empty() {
+        ui.label("No results.");
+    }
+
+    res
+}
+
 /// The system that shows the Miratope library.
 fn show_library(
     egui_ctx: Res<'_, EguiContext>,
     mut query: Query<'_, '_, &mut Concrete>,
     mut poly_name: ResMut<'_, PolyName>,
     mut library: ResMut<'_, Option<Library>>,
+    mut search: ResMut<'_, LibrarySearch>,
     lib_path: Res<'_, LibPath>,
 ) {
     // Shows the polytope library.
@@ -257,8 +343,29 @@ fn show_library(
             .default_width(300.0)
             .max_width(450.0)
             .show(egui_ctx.ctx(), |ui| {
+                // Shows the search box, and searches the library when it's edited.
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+
+                    if ui.text_edit_singleline(&mut search.query).changed() {
+                        search.results = if search.query.is_empty() {
+                            Vec::new()
+                        } else {
+                            library.search(PathBuf::from(lib_path.as_ref()), &search.query)
+                        };
+                    }
+                });
+
+                ui.separator();
+
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
-                    match library.show(ui, PathBuf::from(lib_path.as_ref())) {
+                    let res = if search.query.is_empty() {
+                        library.show(ui, PathBuf::from(lib_path.as_ref()))
+                    } else {
+                        show_search_results(ui, &search.results)
+                    };
+
+                    match res {
                         // No action needs to be taken.
                         ShowResult::None => {}
 
@@ -284,3 +391,63 @@ fn show_library(
             });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an empty directory in the system's temporary folder.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("miratope-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn extensions() {
+        let obj = OsStr::new("obj");
+        assert!(supported_extension(OsStr::new("off")));
+        assert!(!supported_extension(obj));
+
+        let mut extensions = SUPPORTED_EXTENSIONS.to_vec();
+        extensions.push("obj");
+        assert!(extension_in(obj, &extensions));
+    }
+
+    #[test]
+    fn search_nested() {
+        let root = temp_dir("search-nested");
+        let folder = root.join("Polyhedra").join("Regular");
+        fs::create_dir_all(&folder).unwrap();
+        let file = folder.join("Tetrahedron.off");
+        fs::write(&file, "").unwrap();
+
+        let lib = Library::new_folder(&root).unwrap();
+        assert_eq!(lib.search(root.clone(), "TETRA"), vec![file]);
+        assert!(lib.search(root.clone(), "cube").is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn search_depth() {
+        let root = temp_dir("search-depth");
+
+        // The file at the maximum depth is found, the one below it isn't.
+        let mut folder = root.clone();
+        for _ in 1..MAX_SEARCH_DEPTH {
+            folder.push("f");
+        }
+        fs::create_dir_all(folder.join("f")).unwrap();
+        let shallow = folder.join("shallow.off");
+        fs::write(&shallow, "").unwrap();
+        fs::write(folder.join("f").join("deep.off"), "").unwrap();
+
+        let lib = Library::new_folder(&root).unwrap();
+        assert_eq!(lib.search(root.clone(), "shallow"), vec![shallow]);
+        assert!(lib.search(root.clone(), "deep").is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
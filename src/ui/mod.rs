@@ -6,6 +6,7 @@ use bevy_egui::egui::{self, Ui, Widget};
 
 pub mod camera;
 pub mod config;
+pub mod history;
 pub mod library;
 pub mod main_window;
 pub mod memory;
@@ -21,6 +22,7 @@ impl bevy::prelude::PluginGroup for MiratopePlugins {
         group
             .add(camera::InputPlugin)
             .add(config::ConfigPlugin)
+            .add(history::HistoryPlugin)
             .add(window::WindowPlugin)
             .add(library::LibraryPlugin)
             .add(main_window::MainWindowPlugin)
@@ -308,12 +308,16 @@ pub type EguiWindows<'a> = (
     ResMut<'a, StarWindow>,
     ResMut<'a, CompoundWindow>), // Workaround for an argument count limit
     ResMut<'a, TruncateWindow>,
+    ResMut<'a, TruncateDepthWindow>,
     ResMut<'a, ScaleWindow>,
     ResMut<'a, FacetingSettings>,
     ResMut<'a, RotateWindow>,
     ResMut<'a, PlaneWindow>,
     ResMut<'a, TranslateWindow>,
     ResMut<'a, ReflectWindow>,
+    ResMut<'a, CdWindow>,
+    ResMut<'a, ExportWindow>,
+    ResMut<'a, MeasureWindow>,
 );
 
 macro_rules! element_sort {
@@ -361,12 +365,16 @@ pub fn show_top_panel(
         mut star_window,
         mut compound_window),
         mut truncate_window,
+        mut truncate_depth_window,
         mut scale_window,
         mut faceting_settings,
         mut rotate_window,
         mut plane_window,
         mut translate_window,
 	    mut reflect_window,
+        mut cd_window,
+        mut export_window,
+        mut measure_window,
     ): EguiWindows<'_>,
 ) {
     // The top bar.
@@ -385,6 +393,16 @@ pub fn show_top_panel(
                     file_dialog_state.save(poly_name.0.clone());
                 }
 
+                // Exports the polytope in some other format.
+                if ui.button("Export...").clicked() {
+                    export_window.open();
+                }
+
+                // Builds a polytope from a Coxeter diagram.
+                if ui.button("Coxeter diagram...").clicked() {
+                    cd_window.open();
+                }
+
                 if ui.button("Export all memory slots").clicked() {
                     export_memory.0 = true;
                     export_memory.1 = 0;
@@ -481,7 +499,7 @@ pub fn show_top_panel(
                 // Gets the number of flags of the polytope.
                 if ui.button("Flag count").clicked() {
                     if let Some(p) = query.iter_mut().next() {
-                        println!("The polytope has {} flags.", p.flags().count())
+                        println!("The polytope has {} flags.", p.flag_count())
                     }
                 }
 
@@ -525,6 +543,11 @@ pub fn show_top_panel(
                         }
                     }
                 }
+
+                // Shows all of the metrics of the polytope at once.
+                if ui.button("Measure...").clicked() {
+                    measure_window.open();
+                }
             });
 
             menu::menu(ui, "Transform", |ui| {
@@ -628,9 +651,7 @@ pub fn show_top_panel(
                 // Converts the active polytope into its Petrie polygon.
                 if ui.button("Petrie polygon").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
-                        p.element_sort();
-                        let flag = p.first_flag();
-                        match p.petrie_polygon_with(flag) {
+                        match p.petrie_polygon() {
                             Some(q) => {
                                 *p = q;
                                 poly_name.0 = format!("Petrie polygon of {}", poly_name.0);
@@ -751,6 +772,10 @@ pub fn show_top_panel(
                 if ui.button("Truncate...").clicked() {
                     truncate_window.open();
                 }
+
+                if ui.button("Truncate with depth...").clicked() {
+                    truncate_depth_window.open();
+                }
                 
                 ui.separator();
 
@@ -1120,7 +1145,9 @@ fn show_views(
                             .unwrap_or((-1.0, 1.0));
 
                         minmax[i].0 += f64::EPS;
-                        let mut slice = r.cross_section(&hyperplane);
+                        let mut slice = r
+                            .cross_section(&hyperplane)
+                            .unwrap_or_else(Concrete::nullitope);
 
                         if *flatten {
                             slice.flatten_into(&hyperplane.subspace);
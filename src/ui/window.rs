@@ -6,12 +6,18 @@
 use std::marker::PhantomData;
 
 use super::{
+    history::PolyHistory,
     memory::{slot_label, Memory},
     PointWidget,
 };
-use crate::{Concrete, Float, Hypersphere, Point, ui::main_window::PolyName};
+use crate::{Concrete, Float, Hypersphere, Point, EPS, ui::main_window::PolyName};
 
-use miratope_core::{conc::ConcretePolytope, Polytope, abs::Ranked};
+use miratope_core::{
+    conc::ConcretePolytope,
+    cox::cd::{CdResult, Compound},
+    geometry::{rotation, Matrix},
+    Polytope, abs::Ranked,
+};
 
 use bevy::prelude::*;
 use bevy_egui::{
@@ -54,7 +60,11 @@ impl Plugin for WindowPlugin {
             .add_plugin(StarWindow::plugin())
             .add_plugin(CompoundWindow::plugin())
             .add_plugin(TruncateWindow::plugin())
+            .add_plugin(TruncateDepthWindow::plugin())
             .add_plugin(ScaleWindow::plugin())
+            .add_plugin(CdWindow::plugin())
+            .add_plugin(ExportWindow::plugin())
+            .add_plugin(MeasureWindow::plugin())
             .add_plugin(FacetingSettings::plugin())
             .add_plugin(RotateWindow::plugin())
             .add_plugin(PlaneWindow::plugin())
@@ -152,12 +162,14 @@ macro_rules! impl_show {
             egui_ctx: Res<'_, EguiContext>,
             mut query: Query<'_, '_, &mut Concrete>,
             mut poly_name: ResMut<'_, PolyName>,
+            mut history: ResMut<'_, PolyHistory>,
         ) where
             Self: 'static,
         {
             match self_.show(egui_ctx.ctx()) {
                 ShowResult::Ok => {
                     for mut polytope in query.iter_mut() {
+                        history.push((polytope.clone(), poly_name.0.clone()));
                         self_.action(polytope.as_mut());
                     }
                     self_.name_action(&mut poly_name.0);
@@ -340,12 +352,15 @@ pub trait MemoryWindow: Window {
         egui_ctx: Res<'_, EguiContext>,
         mut query: Query<'_, '_, &mut Concrete>,
         memory: Res<'_, Memory>,
+        poly_name: Res<'_, PolyName>,
+        mut history: ResMut<'_, PolyHistory>,
     ) where
         Self: 'static,
     {
         match self_.show(egui_ctx.ctx(), &memory) {
             ShowResult::Ok => {
                 for mut polytope in query.iter_mut() {
+                    history.push((polytope.clone(), poly_name.0.clone()));
                     self_.action(polytope.as_mut());
                 }
                 self_.close()
@@ -533,12 +548,14 @@ pub trait DuoWindow: Window {
         mut query: Query<'_, '_, &mut Concrete>,
         memory: Res<'_, Memory>,
         mut poly_name: ResMut<'_, PolyName>,
+        mut history: ResMut<'_, PolyHistory>,
     ) where
         Self: 'static,
     {
         for mut polytope in query.iter_mut() {
             match self_.show(egui_ctx.ctx(), &polytope, &memory) {
                 ShowResult::Ok => {
+                    history.push((polytope.clone(), poly_name.0.clone()));
                     self_.action(polytope.as_mut(), &memory);
                     self_.name_action(&mut poly_name.0, &memory);
                     self_.close()
@@ -577,6 +594,9 @@ pub struct DualWindow {
 
     /// The radius of the sphere.
     radius: Float,
+
+    /// How close a facet can get to the center before the dual fails.
+    tolerance: Float,
 }
 
 impl Default for DualWindow {
@@ -585,6 +605,7 @@ impl Default for DualWindow {
             open: false,
             center: Point::zeros(0),
             radius: 1.0,
+            tolerance: EPS.sqrt(),
         }
     }
 }
@@ -605,7 +626,7 @@ impl UpdateWindow for DualWindow {
     fn action(&self, polytope: &mut Concrete) {
         let sphere = Hypersphere::with_radius(self.center.clone(), self.radius);
 
-        if let Err(err) = polytope.try_dual_mut_with(&sphere) {
+        if let Err(err) = polytope.try_dual_mut_with_tol(&sphere, self.tolerance) {
             eprintln!("Dual failed: {}", err);
         }
     }
@@ -626,6 +647,16 @@ impl UpdateWindow for DualWindow {
 
             ui.label("Radius");
         });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.tolerance)
+                    .speed(0.0001)
+                    .clamp_range(0.0..=Float::MAX),
+            );
+
+            ui.label("Tolerance");
+        });
     }
 
     fn dim(&self) -> usize {
@@ -635,7 +666,6 @@ impl UpdateWindow for DualWindow {
     fn default_with(dim: usize) -> Self {
         Self {
             center: Point::zeros(dim),
-            radius: 1.0,
             ..Default::default()
         }
     }
@@ -909,7 +939,11 @@ impl UpdateWindow for AntiprismWindow {
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Dual of {}", name);
+        if self.retroprism {
+            *name = format!("Retroprism of {}", name);
+        } else {
+            *name = format!("Antiprism of {}", name);
+        }
     }
 
     fn build(&mut self, ui: &mut Ui) {
@@ -1497,14 +1531,80 @@ impl UpdateWindow for TruncateWindow {
     }
 }
 
-/// A window that scales a polytope.
-#[derive(Default)]
+/// A window that truncates a polytope by moving its vertices along its edges.
+/// Unlike the [`TruncateWindow`], it works the same in any dimension.
+pub struct TruncateDepthWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The depth of the truncation, from 0 (rectification) to 1 (no
+    /// truncation at all).
+    depth: f64,
+}
+
+impl Default for TruncateDepthWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            depth: 1.0 / 3.0,
+        }
+    }
+}
+
+impl Window for TruncateDepthWindow {
+    const NAME: &'static str = "Truncate with depth";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for TruncateDepthWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        *polytope = polytope.truncate_at(self.depth);
+    }
+
+    fn name_action(&self, name: &mut String) {
+        if self.depth <= 0.0 {
+            *name = format!("Rectified {}", name);
+        } else if self.depth < 1.0 {
+            *name = format!("Truncated {}", name);
+        }
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.add(egui::Slider::new(&mut self.depth, 0.0..=1.0).text("Depth"));
+    }
+}
+
+/// A window that scales a polytope, either uniformly or along each axis.
 pub struct ScaleWindow {
     /// Whether the window is open.
     open: bool,
 
-    /// The scale factor.
+    /// The uniform scale factor.
     scale: f64,
+
+    /// Whether to also scale each axis by its own factor.
+    per_axis: bool,
+
+    /// The scale factors for each axis.
+    axes: Vec<f64>,
+}
+
+impl Default for ScaleWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            scale: 1.0,
+            per_axis: false,
+            axes: Vec::new(),
+        }
+    }
 }
 
 impl Window for ScaleWindow {
@@ -1519,9 +1619,17 @@ impl Window for ScaleWindow {
     }
 }
 
-impl PlainWindow for ScaleWindow {
+impl UpdateWindow for ScaleWindow {
     fn action(&self, polytope: &mut Concrete) {
         polytope.scale(self.scale);
+
+        if self.per_axis {
+            for v in polytope.vertices_mut() {
+                for (x, &factor) in v.iter_mut().zip(&self.axes) {
+                    *x *= factor;
+                }
+            }
+        }
     }
 
     fn name_action(&self, _name: &mut String) {}
@@ -1532,8 +1640,477 @@ impl PlainWindow for ScaleWindow {
                 egui::DragValue::new(&mut self.scale)
                     .speed(0.01)
             );
+
+            ui.label("Scale");
+        });
+
+        ui.add(egui::Checkbox::new(&mut self.per_axis, "Scale each axis"));
+
+        if self.per_axis {
+            for (i, factor) in self.axes.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(factor)
+                            .speed(0.01)
+                            .clamp_range(0.0..=Float::MAX),
+                    );
+
+                    ui.label(format!("Axis {}", i));
+                });
+            }
+        }
+    }
+
+    fn dim(&self) -> usize {
+        self.axes.len()
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            axes: vec![1.0; dim],
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        self.axes.resize(dim, 1.0);
+    }
+}
+
+/// A window that builds a Wythoffian polytope, or a compound of them, out of a
+/// Coxeter diagram typed by the user.
+#[derive(Default)]
+pub struct CdWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The diagram typed by the user.
+    diagram: String,
+
+    /// The diagram parsed from the text, or the error found while parsing or
+    /// validating it. This is `None` when the diagram is empty.
+    compound: Option<CdResult<Compound>>,
+}
+
+impl CdWindow {
+    /// Parses and validates the diagram after it has been edited. The polytope
+    /// itself is only built once the user presses Ok, since that can get
+    /// expensive.
+    fn reparse(&mut self) {
+        self.compound = if self.diagram.trim().is_empty() {
+            None
+        } else {
+            Some(Compound::parse(&self.diagram).and_then(|compound| {
+                compound.check()?;
+                Ok(compound)
+            }))
+        };
+    }
+}
+
+impl Window for CdWindow {
+    const NAME: &'static str = "Coxeter diagram";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for CdWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        if let Some(Ok(compound)) = &self.compound {
+            match compound.build() {
+                Ok(poly) => *polytope = poly,
+                Err(err) => eprintln!("Coxeter diagram failed: {}", err),
+            }
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        if let Some(Ok(_)) = self.compound {
+            *name = self.diagram.trim().to_string();
+        }
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui.add(egui::TextEdit::singleline(&mut self.diagram)).changed() {
+                self.reparse();
+            }
+
+            ui.label("Diagram");
+        });
+
+        // Shows the error inline.
+        if let Some(Err(err)) = &self.compound {
+            ui.colored_label(egui::Color32::RED, err.to_string());
+        }
+    }
+}
+
+/// The file formats a polytope can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// An OFF file.
+    Off,
+
+    /// A GeoGebra file.
+    Ggb,
+
+    /// The debug representation of the polytope, as plain text.
+    Debug,
+}
+
+impl Default for ExportFormat {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl ExportFormat {
+    /// All of the export formats, in the order they're shown.
+    const ALL: [Self; 3] = [Self::Off, Self::Ggb, Self::Debug];
+
+    /// The name of the format, as shown on the dropdown.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Off => "OFF",
+            Self::Ggb => "GeoGebra",
+            Self::Debug => "Debug",
+        }
+    }
+
+    /// The default extension for files in this format.
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Ggb => "ggb",
+            Self::Debug => "txt",
+        }
+    }
+
+    /// Serializes a polytope in this format.
+    fn serialize(self, poly: &Concrete) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Off => poly
+                .to_off(Default::default())
+                .map(String::into_bytes)
+                .map_err(|err| err.to_string()),
+            Self::Ggb => poly.to_ggb().map_err(|err| err.to_string()),
+            Self::Debug => Ok(format!("{:#?}", poly).into_bytes()),
+        }
+    }
+}
+
+/// A window that saves the polytope on screen into a file, in any of the
+/// supported formats. Unlike the other windows, it stays open after exporting,
+/// so that any errors can be shown inline.
+#[derive(Default)]
+pub struct ExportWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The format to export to.
+    format: ExportFormat,
+
+    /// The path of the file to write. If empty, the polytope's name is used.
+    file_path: String,
+
+    /// The path written to by the last export, or the error it found.
+    result: Option<Result<String, String>>,
+}
+
+impl Window for ExportWindow {
+    const NAME: &'static str = "Export";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl ExportWindow {
+    /// Returns the default name of the file a polytope is exported to. Path
+    /// separators in the polytope's name, as in `x5/2o3o`, are replaced so that
+    /// the file is always written to the working directory.
+    fn default_path(&self, name: &str) -> String {
+        let name = name.replace(|c: char| c == '/' || c == '\\', "_");
+        format!("{}.{}", name, self.format.extension())
+    }
+
+    /// Writes a polytope into a file, returning the path written to.
+    fn export(&self, poly: &Concrete, name: &str) -> Result<String, String> {
+        let path = if self.file_path.trim().is_empty() {
+            self.default_path(name)
+        } else {
+            self.file_path.trim().to_string()
+        };
+
+        std::fs::write(&path, self.format.serialize(poly)?).map_err(|err| err.to_string())?;
+        Ok(path)
+    }
+
+    /// Builds the window to be shown on screen.
+    fn build(&mut self, ui: &mut Ui) {
+        let format = &mut self.format;
+        egui::ComboBox::from_label("Format")
+            .selected_text(format.name())
+            .show_ui(ui, |ui| {
+                for option in ExportFormat::ALL {
+                    ui.selectable_value(format, option, option.name());
+                }
+            });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.file_path));
+            ui.label("File path");
+        });
+
+        // Shows the result of the last export inline.
+        match &self.result {
+            Some(Ok(path)) => {
+                ui.label(format!("Saved to {}", path));
+            }
+            Some(Err(err)) => {
+                ui.colored_label(egui::Color32::RED, err);
+            }
+            None => {}
+        }
+    }
+
+    /// The system that shows the window. The export happens here, since it
+    /// needs to record its result in the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        query: Query<'_, '_, &Concrete>,
+        poly_name: Res<'_, PolyName>,
+    ) {
+        let mut open = self_.is_open();
+        let mut result = ShowResult::None;
+
+        egui::Window::new(Self::NAME)
+            .open(&mut open)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                self_.build(ui);
+                ui.add(OkReset::new(&mut result));
+            });
+
+        if !open {
+            self_.close();
+            return;
+        }
+
+        match result {
+            ShowResult::Ok => {
+                if let Some(poly) = query.iter().next() {
+                    let export = self_.export(poly, &poly_name.0);
+                    self_.result = Some(export);
+                }
+            }
+            ShowResult::Reset => {
+                *self_ = Default::default();
+                self_.open();
+            }
+            ShowResult::Close | ShowResult::None => {}
+        }
+    }
+
+    /// A plugin that adds the resource for the window and the system to show
+    /// it.
+    pub fn plugin() -> ExportWindowPlugin {
+        ExportWindowPlugin
+    }
+}
+
+/// A plugin that adds all of the necessary systems for the [`ExportWindow`].
+pub struct ExportWindowPlugin;
+
+impl Plugin for ExportWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExportWindow>()
+            .add_system(ExportWindow::show_system.system().label("show_windows"));
+    }
+}
+
+/// The metrics of a polytope shown on the [`MeasureWindow`].
+struct Measurements {
+    /// The number of vertices.
+    vertices: usize,
+
+    /// The number of edges.
+    edges: usize,
+
+    /// The number of 2-elements.
+    faces: usize,
+
+    /// The volume of the polytope, if defined.
+    volume: Option<f64>,
+
+    /// The circumradius of the polytope, if it's circumscribable.
+    circumradius: Option<f64>,
+
+    /// The inradius of the polytope, if defined.
+    inradius: Option<f64>,
+
+    /// Whether the polytope is convex.
+    convex: bool,
+
+    /// Whether the polytope is regular, or `None` if computing its symmetry
+    /// group went over budget.
+    regular: Option<bool>,
+}
+
+impl Measurements {
+    /// Computes all of the metrics of a polytope.
+    fn new(poly: &Concrete) -> Self {
+        let mut poly = poly.clone();
+        poly.element_sort();
+
+        Self {
+            vertices: poly.vertex_count(),
+            edges: poly.edge_count(),
+            faces: poly.el_count(3),
+            volume: poly.volume(),
+            circumradius: poly.circumradius(),
+            inradius: poly.inradius(),
+            convex: poly.is_convex(),
+            regular: poly.try_is_regular(None).ok(),
+        }
+    }
+}
+
+/// Formats a metric that might be undefined.
+fn fmt_metric(value: Option<f64>) -> String {
+    match value {
+        Some(value) => format!("{:.6}", value),
+        None => "undefined".to_string(),
+    }
+}
+
+/// A read-only window showing some metrics of the polytope on screen. The
+/// metrics are only computed while the window is open, and are recomputed
+/// whenever the polytope changes.
+#[derive(Default)]
+pub struct MeasureWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The metrics of the polytope on screen, or `None` if they need to be
+    /// recomputed.
+    measurements: Option<Measurements>,
+}
+
+impl Window for MeasureWindow {
+    const NAME: &'static str = "Measure";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl MeasureWindow {
+    /// Builds the window to be shown on screen.
+    fn build(&self, ui: &mut Ui) {
+        let m = match &self.measurements {
+            Some(m) => m,
+            None => return,
+        };
+
+        egui::Grid::new("measurements").show(ui, |ui| {
+            let mut row = |label: &str, value: String| {
+                ui.label(label);
+                ui.label(value);
+                ui.end_row();
+            };
+
+            row("Vertices", m.vertices.to_string());
+            row("Edges", m.edges.to_string());
+            row("Faces", m.faces.to_string());
+            row("Volume", fmt_metric(m.volume));
+            row("Circumradius", fmt_metric(m.circumradius));
+            row("Inradius", fmt_metric(m.inradius));
+            row("Convex", m.convex.to_string());
+            row(
+                "Regular",
+                m.regular.map_or_else(|| "unknown".to_string(), |regular| regular.to_string()),
+            );
         });
     }
+
+    /// The system that shows the window, computing the metrics if needed.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        query: Query<'_, '_, &Concrete>,
+    ) {
+        if !self_.is_open() {
+            return;
+        }
+
+        if self_.measurements.is_none() {
+            if let Some(poly) = query.iter().next() {
+                self_.measurements = Some(Measurements::new(poly));
+            }
+        }
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new(Self::NAME)
+            .open(&mut open)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                self_.build(ui);
+
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    close = ui.button("Close").clicked();
+                });
+            });
+
+        if !open || close {
+            self_.close();
+        }
+    }
+
+    /// The system that discards the metrics when the polytope is changed.
+    fn update_system(
+        mut self_: ResMut<'_, Self>,
+        query: Query<'_, '_, (), Changed<Concrete>>,
+    ) {
+        if query.iter().next().is_some() {
+            self_.measurements = None;
+        }
+    }
+
+    /// A plugin that adds the resource for the window and the systems to show
+    /// and update it.
+    pub fn plugin() -> MeasureWindowPlugin {
+        MeasureWindowPlugin
+    }
+}
+
+/// A plugin that adds all of the necessary systems for the [`MeasureWindow`].
+pub struct MeasureWindowPlugin;
+
+impl Plugin for MeasureWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MeasureWindow>()
+            .add_system(MeasureWindow::show_system.system().label("show_windows"))
+            .add_system(MeasureWindow::update_system.system().label("show_windows"));
+    }
 }
 
 /// Where to get the symmetry group for faceting
@@ -1890,6 +2467,12 @@ impl MemoryWindow for FacetingSettings {
 }
 
 
+/// The number of coordinate planes in a space of a given dimension, each of
+/// which gets its own rotation in the [`RotateWindow`].
+fn rotation_count(dim: usize) -> usize {
+    dim * dim.saturating_sub(1) / 2
+}
+
 /// Rotation window for Transform tab
 #[derive(Default)]
 pub struct RotateWindow {
@@ -1921,30 +2504,23 @@ impl Window for RotateWindow {
 impl UpdateWindow for RotateWindow {
     fn action(&self, polytope: &mut Concrete) {
         if self.rank > 1 {
-            polytope.element_sort();
-            
+            // Composes the rotations in each coordinate plane.
+            let mut matrix = Matrix::identity(self.rank, self.rank);
             let mut index = 0;
             for r in 0..self.rank-1 {
-                for s in (r+1)..=self.rank-1 {
-                    for v in polytope.vertices_mut() {
-                        
-                        let theta: f64;
-                        if self.degcheck { //Degrees
-                            theta = self.rots[index] * 0.017453292519943295;
-                        }
-                        else { //Radians
-                            theta = self.rots[index];
-                        }
-                        
-                        let x = v[r]*theta.cos() - v[s]*theta.sin();
-                        let y = v[r]*theta.sin() + v[s]*theta.cos();
-                        v[r] = x;
-                        v[s] = y;
-                    }
-                    index += 1; //Setting next index value
+                for s in (r+1)..self.rank {
+                    let theta = if self.degcheck {
+                        self.rots[index].to_radians()
+                    } else {
+                        self.rots[index]
+                    };
+
+                    matrix = rotation(self.rank, r, s, theta) * matrix;
+                    index += 1;
                 }
             }
-            
+
+            *polytope = polytope.clone().apply(&matrix);
             println!("Object rotated!");
         }
         else {
@@ -1959,8 +2535,8 @@ impl UpdateWindow for RotateWindow {
     fn build(&mut self, ui: &mut Ui) {
         let mut index = 0;
         ui.add(egui::Checkbox::new(&mut self.degcheck, "Use degrees instead of radians"));
-        for r in 0..self.rank-1 {
-            for s in (r+1)..=self.rank-1 {
+        for r in 0..self.rank.saturating_sub(1) {
+            for s in (r+1)..self.rank {
                 ui.horizontal(|ui| {
                     if self.degcheck {
                         ui.add(egui::DragValue::new(&mut self.rots[ index ]).speed(1.0).clamp_range::<f64>(0.0..=360.0));
@@ -1983,14 +2559,14 @@ impl UpdateWindow for RotateWindow {
     fn default_with(dim: usize) -> Self {
         Self {
             rank: dim,
-            rots: vec![0.0001; dim*(dim+1)/2], // if this is set to 0 the whole window becomes dark for some reason
+            rots: vec![0.0001; rotation_count(dim)], // if this is set to 0 the whole window becomes dark for some reason
             ..Default::default()
         }
     }
 
     fn update(&mut self, dim: usize) {
         self.rank = dim;
-        self.rots = vec![0.0; dim*(dim+1)/2];
+        self.rots = vec![0.0; rotation_count(dim)];
     }
 }
 
@@ -2360,4 +2936,32 @@ impl UpdateWindow for ReflectWindow {
         self.rank = dim;
         self.normal = Point::zeros(dim);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that polytope names with slashes give valid file names.
+    #[test]
+    fn export_default_path() {
+        let window = ExportWindow::default();
+        assert_eq!(window.default_path("Cube"), "Cube.off");
+        assert_eq!(window.default_path("x5/2o3o"), "x5_2o3o.off");
+        assert_eq!(window.default_path("a\\b"), "a_b.off");
+    }
+
+    /// Checks the names given to antiprisms and retroprisms.
+    #[test]
+    fn antiprism_name() {
+        let mut window = AntiprismWindow::default();
+        let mut name = String::from("Square");
+        window.name_action(&mut name);
+        assert_eq!(name, "Antiprism of Square");
+
+        window.retroprism = true;
+        let mut name = String::from("Square");
+        window.name_action(&mut name);
+        assert_eq!(name, "Retroprism of Square");
+    }
+}